@@ -118,6 +118,10 @@ pub mod rpc {
     #[doc(inline)]
     pub use alloy_rpc_client as client;
 
+    #[cfg(all(feature = "rpc-client", feature = "reqwest"))]
+    #[doc(inline)]
+    pub use alloy_rpc_client::ReqwestClient;
+
     #[cfg(feature = "json-rpc")]
     #[doc(inline)]
     pub use alloy_json_rpc as json_rpc;
@@ -133,6 +133,30 @@ impl<E, ErrResp> RpcError<E, ErrResp> {
         matches!(self, Self::LocalUsageError(_))
     }
 
+    /// Check if the error occurred at the transport level, i.e. while
+    /// communicating with the server.
+    ///
+    /// This is the case for [`RpcError::Transport`].
+    pub const fn is_transport_level(&self) -> bool {
+        self.is_transport_error()
+    }
+
+    /// Check if the error occurred at the RPC level, i.e. the server
+    /// processed the request and returned an error response.
+    ///
+    /// This is the case for [`RpcError::ErrorResp`].
+    pub const fn is_rpc_level(&self) -> bool {
+        self.is_error_resp()
+    }
+
+    /// Returns the JSON-RPC error code, if this is an error response.
+    pub const fn rpc_error_code(&self) -> Option<i64> {
+        match self {
+            Self::ErrorResp(err) => Some(err.code),
+            _ => None,
+        }
+    }
+
     /// Fallible conversion to an error response.
     pub const fn as_error_resp(&self) -> Option<&ErrorPayload<ErrResp>> {
         match self {
@@ -187,3 +211,23 @@ impl<E> RpcError<E, Box<RawValue>> {
         self.parse_data()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classify_levels() {
+        let payload: ErrorPayload =
+            serde_json::from_str(r#"{"code":-32000,"message":"execution reverted"}"#).unwrap();
+        let err: RpcError<()> = RpcError::ErrorResp(payload);
+        assert!(err.is_rpc_level());
+        assert!(!err.is_transport_level());
+        assert_eq!(err.rpc_error_code(), Some(-32000));
+
+        let err: RpcError<()> = RpcError::Transport(());
+        assert!(err.is_transport_level());
+        assert!(!err.is_rpc_level());
+        assert_eq!(err.rpc_error_code(), None);
+    }
+}
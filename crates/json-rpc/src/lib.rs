@@ -89,10 +89,15 @@ mod notification;
 pub use notification::{EthNotification, PubSubItem, SubId};
 
 mod packet;
-pub use packet::{BorrowedResponsePacket, RequestPacket, ResponsePacket};
+pub use packet::{
+    validate_batch_size, BatchTooLargeError, BorrowedResponsePacket, RequestPacket, ResponseBatch,
+    ResponsePacket,
+};
 
 mod request;
-pub use request::{PartiallySerializedRequest, Request, RequestMeta, SerializedRequest};
+pub use request::{
+    PartiallySerializedRequest, Request, RequestMeta, ResponseSizeLimit, SerializedRequest,
+};
 
 mod response;
 pub use response::{
@@ -164,3 +169,49 @@ impl<T> RpcObject for T where T: RpcSend + RpcRecv {}
 pub trait BorrowedRpcObject<'de>: RpcBorrow<'de> + RpcSend {}
 
 impl<'de, T> BorrowedRpcObject<'de> for T where T: RpcBorrow<'de> + RpcSend {}
+
+/// A typed JSON-RPC method.
+///
+/// Implementers describe a single RPC method: its name, the parameters it is
+/// sent with, and the type it returns. This allows dispatching compile-time
+/// checked calls, e.g. via `RpcClient::call_typed` in `alloy-rpc-client`.
+///
+/// ```
+/// use alloy_json_rpc::RpcMethod;
+/// use alloy_primitives::{Address, U256, U64};
+///
+/// /// `eth_blockNumber`
+/// struct EthBlockNumber;
+///
+/// impl RpcMethod for EthBlockNumber {
+///     const METHOD: &'static str = "eth_blockNumber";
+///     type Params = ();
+///     type Return = U64;
+///
+///     fn into_params(self) -> Self::Params {}
+/// }
+///
+/// /// `eth_getBalance`
+/// struct EthGetBalance(Address);
+///
+/// impl RpcMethod for EthGetBalance {
+///     const METHOD: &'static str = "eth_getBalance";
+///     type Params = (Address, &'static str);
+///     type Return = U256;
+///
+///     fn into_params(self) -> Self::Params {
+///         (self.0, "latest")
+///     }
+/// }
+/// ```
+pub trait RpcMethod {
+    /// The method name.
+    const METHOD: &'static str;
+    /// The request parameters.
+    type Params: RpcSend;
+    /// The response type.
+    type Return: RpcRecv;
+
+    /// Converts the call into its request parameters.
+    fn into_params(self) -> Self::Params;
+}
@@ -41,12 +41,24 @@ pub struct EthNotification<T = Box<serde_json::value::RawValue>> {
 ///
 /// Ethereum pubsub uses a non-standard JSON-RPC notification format. An item received over a pubsub
 /// transport may be a JSON-RPC response or an Ethereum-style notification.
+///
+/// This enum is non-exhaustive, so that new kinds of items can be added
+/// without a breaking change.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum PubSubItem {
     /// A [`Response`] to a JSON-RPC request.
     Response(Response),
     /// An Ethereum-style notification.
     Notification(EthNotification),
+    /// A JSON-RPC notification with a method other than `eth_subscription`,
+    /// e.g. a vendor-specific push notification.
+    Other {
+        /// The notification method.
+        method: String,
+        /// The notification params.
+        params: Box<serde_json::value::RawValue>,
+    },
 }
 
 impl From<Response> for PubSubItem {
@@ -81,8 +93,9 @@ impl<'de> Deserialize<'de> for PubSubItem {
             {
                 let mut id = None;
                 let mut result = None;
-                let mut params = None;
+                let mut params: Option<Box<serde_json::value::RawValue>> = None;
                 let mut error = None;
+                let mut method: Option<String> = None;
 
                 // Drain the map into the appropriate fields.
                 while let Ok(Some(key)) = map.next_key() {
@@ -111,6 +124,12 @@ impl<'de> Deserialize<'de> for PubSubItem {
                             }
                             error = Some(map.next_value()?);
                         }
+                        "method" => {
+                            if method.is_some() {
+                                return Err(serde::de::Error::duplicate_field("method"));
+                            }
+                            method = Some(map.next_value()?);
+                        }
                         // Discard unknown fields.
                         _ => {
                             let _: serde::de::IgnoredAny = map.next_value()?;
@@ -137,9 +156,15 @@ impl<'de> Deserialize<'de> for PubSubItem {
                             "unexpected `error` field in subscription notification",
                         ));
                     }
-                    params
-                        .map(PubSubItem::Notification)
-                        .ok_or_else(|| serde::de::Error::missing_field("params"))
+                    let params = params.ok_or_else(|| serde::de::Error::missing_field("params"))?;
+                    match method {
+                        Some(method) if method != "eth_subscription" => {
+                            Ok(PubSubItem::Other { method, params })
+                        }
+                        _ => serde_json::from_str(params.get())
+                            .map(PubSubItem::Notification)
+                            .map_err(serde::de::Error::custom),
+                    }
                 }
             }
         }
@@ -281,6 +306,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deserializer_test_other_notification() {
+        let notification = r#"
+            {
+                "jsonrpc": "2.0",
+                "method": "vendor_ping",
+                "params": ["0x1"]
+            }"#;
+
+        let deser = serde_json::from_str::<PubSubItem>(notification).unwrap();
+
+        match deser {
+            PubSubItem::Other { method, params } => {
+                assert_eq!(method, "vendor_ping");
+                assert_eq!(params.get(), r#"["0x1"]"#);
+            }
+            _ => panic!("unexpected deserialization result"),
+        }
+    }
+
     #[test]
     fn deserializer_test_invalid_structure() {
         // An invalid structure should fail deserialization
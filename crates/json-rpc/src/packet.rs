@@ -1,12 +1,47 @@
-use crate::{ErrorPayload, Id, Response, ResponsePayload, SerializedRequest};
-use alloy_primitives::map::HashSet;
+use crate::{
+    ErrorPayload, Id, Request, Response, ResponsePayload, ResponseSizeLimit, SerializedRequest,
+};
+use alloy_primitives::map::{HashMap, HashSet};
 use http::HeaderMap;
 use serde::{
     de::{self, Deserializer, MapAccess, SeqAccess, Visitor},
     Deserialize, Serialize,
 };
 use serde_json::value::RawValue;
-use std::{borrow::Borrow, fmt, hash::Hash, marker::PhantomData};
+use std::{
+    borrow::Borrow,
+    fmt,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::OnceLock,
+};
+
+/// Error returned by [`validate_batch_size`] when a batch holds more requests
+/// than allowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("batch of {actual} requests exceeds the maximum batch size of {max}")]
+pub struct BatchTooLargeError {
+    /// The number of requests in the batch.
+    pub actual: usize,
+    /// The maximum number of requests allowed in a batch.
+    pub max: usize,
+}
+
+/// Check that a batch of requests does not exceed `max_size` requests.
+///
+/// Many nodes reject batches over a certain size, commonly 100 requests. This
+/// can be called before serializing the batch, to fail early. Oversized
+/// packets can be split with [`RequestPacket::chunk`].
+pub const fn validate_batch_size<S>(
+    requests: &[Request<S>],
+    max_size: usize,
+) -> Result<(), BatchTooLargeError> {
+    if requests.len() > max_size {
+        return Err(BatchTooLargeError { actual: requests.len(), max: max_size });
+    }
+    Ok(())
+}
 
 /// A [`RequestPacket`] is a [`SerializedRequest`] or a batch of serialized
 /// request.
@@ -112,6 +147,65 @@ impl RequestPacket {
         }
     }
 
+    /// Merge two packets into a single batch, with the requests of `other`
+    /// following the requests of `self`.
+    ///
+    /// The result is always a [`RequestPacket::Batch`], even when merging two
+    /// [`RequestPacket::Single`] packets or an empty batch.
+    pub fn merge(self, other: Self) -> Self {
+        let mut requests = self.into_vec();
+        requests.extend(other.into_vec());
+        Self::Batch(requests)
+    }
+
+    /// Split the packet into two batches at the given index.
+    ///
+    /// The first batch contains the first `n` requests, and the second batch
+    /// contains the rest. A [`RequestPacket::Single`] is treated as a batch of
+    /// one request.
+    ///
+    /// Either batch may be empty: the first if `n` is zero, the second if
+    /// `n` is greater than or equal to the number of requests. Servers reject
+    /// empty batches, so check [`is_empty`](Self::is_empty) before sending
+    /// them.
+    pub fn split_at(self, n: usize) -> (Self, Self) {
+        let mut first = self.into_vec();
+        let second = first.split_off(n.min(first.len()));
+        (Self::Batch(first), Self::Batch(second))
+    }
+
+    /// Split the packet into batches of at most `max_size` requests, to be
+    /// sent sequentially.
+    ///
+    /// A [`RequestPacket::Single`] is returned as-is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is zero.
+    pub fn chunk(self, max_size: usize) -> impl Iterator<Item = Self> {
+        assert!(max_size > 0, "batch chunk size must be greater than zero");
+        let chunks = match self {
+            Self::Single(req) => vec![Self::Single(req)],
+            Self::Batch(reqs) => {
+                let mut reqs = reqs.into_iter();
+                std::iter::from_fn(|| {
+                    let chunk: Vec<_> = reqs.by_ref().take(max_size).collect();
+                    (!chunk.is_empty()).then_some(Self::Batch(chunk))
+                })
+                .collect()
+            }
+        };
+        chunks.into_iter()
+    }
+
+    /// Consume the packet, returning its requests.
+    fn into_vec(self) -> Vec<SerializedRequest> {
+        match self {
+            Self::Single(req) => vec![req],
+            Self::Batch(reqs) => reqs,
+        }
+    }
+
     /// Returns all [`SerializedRequest`].
     pub const fn requests(&self) -> &[SerializedRequest] {
         match self {
@@ -147,6 +241,16 @@ impl RequestPacket {
 
         HeaderMap::new()
     }
+
+    /// Retrieves the smallest [`ResponseSizeLimit`] attached to the requests'
+    /// metadata, if any.
+    pub fn response_size_limit(&self) -> Option<usize> {
+        self.requests()
+            .iter()
+            .filter_map(|req| req.meta().extensions().get::<ResponseSizeLimit>())
+            .map(|limit| limit.0)
+            .min()
+    }
 }
 
 /// A [`ResponsePacket`] is a [`Response`] or a batch of responses.
@@ -155,7 +259,153 @@ pub enum ResponsePacket<Payload = Box<RawValue>, ErrData = Box<RawValue>> {
     /// A single response.
     Single(Response<Payload, ErrData>),
     /// A batch of responses.
-    Batch(Vec<Response<Payload, ErrData>>),
+    Batch(ResponseBatch<Payload, ErrData>),
+}
+
+/// Batches with at least this many responses are indexed by ID on the first
+/// lookup. Smaller batches are searched linearly.
+const INDEX_THRESHOLD: usize = 32;
+
+/// A batch of [`Response`]s.
+///
+/// This derefs to the underlying [`Vec`]. Large batches lazily build an index
+/// of the response IDs for [`find_by_id`](Self::find_by_id), which is dropped
+/// whenever the batch is accessed mutably.
+#[derive(Clone)]
+pub struct ResponseBatch<Payload = Box<RawValue>, ErrData = Box<RawValue>> {
+    responses: Vec<Response<Payload, ErrData>>,
+    /// The position of the first response for each ID.
+    index: OnceLock<HashMap<Id, usize>>,
+}
+
+impl<Payload, ErrData> Default for ResponseBatch<Payload, ErrData> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<Payload: fmt::Debug, ErrData: fmt::Debug> fmt::Debug for ResponseBatch<Payload, ErrData> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.responses.fmt(f)
+    }
+}
+
+impl<Payload, ErrData> ResponseBatch<Payload, ErrData> {
+    /// Create a new batch of responses.
+    pub const fn new(responses: Vec<Response<Payload, ErrData>>) -> Self {
+        Self { responses, index: OnceLock::new() }
+    }
+
+    /// Returns the responses as a slice.
+    pub const fn as_slice(&self) -> &[Response<Payload, ErrData>] {
+        self.responses.as_slice()
+    }
+
+    /// Consume the batch, returning the responses.
+    pub fn into_inner(self) -> Vec<Response<Payload, ErrData>> {
+        self.responses
+    }
+
+    /// Find the first response with the given ID.
+    ///
+    /// See [`ResponsePacket::find_by_id`].
+    pub fn find_by_id(&self, id: &Id) -> Option<&Response<Payload, ErrData>> {
+        if self.responses.len() < INDEX_THRESHOLD {
+            return self.responses.iter().find(|resp| resp.id == *id);
+        }
+        let index = self.index.get_or_init(|| {
+            let mut index =
+                HashMap::with_capacity_and_hasher(self.responses.len(), Default::default());
+            for (i, resp) in self.responses.iter().enumerate() {
+                index.entry(resp.id.clone()).or_insert(i);
+            }
+            index
+        });
+        index.get(id).map(|&i| &self.responses[i])
+    }
+
+    /// Find the first response with the given ID, mutably.
+    ///
+    /// The index is used if it has been built, and dropped, as the ID of the
+    /// returned response may be changed.
+    pub fn find_by_id_mut(&mut self, id: &Id) -> Option<&mut Response<Payload, ErrData>> {
+        let pos = match self.index.take() {
+            Some(index) => index.get(id).copied(),
+            None => self.responses.iter().position(|resp| resp.id == *id),
+        };
+        pos.map(|i| &mut self.responses[i])
+    }
+}
+
+impl<Payload, ErrData> Deref for ResponseBatch<Payload, ErrData> {
+    type Target = Vec<Response<Payload, ErrData>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.responses
+    }
+}
+
+impl<Payload, ErrData> DerefMut for ResponseBatch<Payload, ErrData> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.index.take();
+        &mut self.responses
+    }
+}
+
+impl<Payload, ErrData> From<Vec<Response<Payload, ErrData>>> for ResponseBatch<Payload, ErrData> {
+    fn from(responses: Vec<Response<Payload, ErrData>>) -> Self {
+        Self::new(responses)
+    }
+}
+
+impl<Payload, ErrData> From<ResponseBatch<Payload, ErrData>> for Vec<Response<Payload, ErrData>> {
+    fn from(batch: ResponseBatch<Payload, ErrData>) -> Self {
+        batch.into_inner()
+    }
+}
+
+impl<Payload, ErrData> FromIterator<Response<Payload, ErrData>>
+    for ResponseBatch<Payload, ErrData>
+{
+    fn from_iter<T: IntoIterator<Item = Response<Payload, ErrData>>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<Payload, ErrData> IntoIterator for ResponseBatch<Payload, ErrData> {
+    type Item = Response<Payload, ErrData>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.responses.into_iter()
+    }
+}
+
+impl<'a, Payload, ErrData> IntoIterator for &'a ResponseBatch<Payload, ErrData> {
+    type Item = &'a Response<Payload, ErrData>;
+    type IntoIter = std::slice::Iter<'a, Response<Payload, ErrData>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.responses.iter()
+    }
+}
+
+impl<'a, Payload, ErrData> IntoIterator for &'a mut ResponseBatch<Payload, ErrData> {
+    type Item = &'a mut Response<Payload, ErrData>;
+    type IntoIter = std::slice::IterMut<'a, Response<Payload, ErrData>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
+}
+
+impl<Payload: Serialize, ErrData: Serialize> Serialize for ResponseBatch<Payload, ErrData> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.responses.serialize(serializer)
+    }
 }
 
 impl<Payload, ErrData> FromIterator<Response<Payload, ErrData>>
@@ -171,10 +421,10 @@ impl<Payload, ErrData> FromIterator<Response<Payload, ErrData>>
                 let mut batch = Vec::new();
                 batch.push(first);
                 batch.extend(iter);
-                Self::Batch(batch)
+                Self::Batch(batch.into())
             };
         }
-        Self::Batch(vec![])
+        Self::Batch(ResponseBatch::default())
     }
 }
 
@@ -183,7 +433,7 @@ impl<Payload, ErrData> From<Vec<Response<Payload, ErrData>>> for ResponsePacket<
         if value.len() == 1 {
             Self::Single(value.into_iter().next().unwrap())
         } else {
-            Self::Batch(value)
+            Self::Batch(value.into())
         }
     }
 }
@@ -222,7 +472,7 @@ where
                     responses.push(response);
                 }
 
-                Ok(ResponsePacket::Batch(responses))
+                Ok(ResponsePacket::Batch(responses.into()))
             }
 
             fn visit_map<M>(self, map: M) -> Result<Self::Value, M::Error>
@@ -357,6 +607,37 @@ impl<Payload, ErrData> ResponsePacket<Payload, ErrData> {
         self.responses().iter().map(|resp| &resp.id)
     }
 
+    /// Returns a mutable slice of all [`Response`]s.
+    pub fn responses_mut(&mut self) -> &mut [Response<Payload, ErrData>] {
+        match self {
+            Self::Single(req) => std::slice::from_mut(req),
+            Self::Batch(req) => req.as_mut_slice(),
+        }
+    }
+
+    /// Find the response with the given ID.
+    ///
+    /// If the packet contains duplicate IDs, the first matching response is
+    /// returned. Small batches are searched linearly, while large batches
+    /// build an index of the response IDs on the first lookup. To look up
+    /// many IDs at once, see [`responses_by_ids`](Self::responses_by_ids).
+    pub fn find_by_id(&self, id: &Id) -> Option<&Response<Payload, ErrData>> {
+        match self {
+            Self::Single(single) => (single.id == *id).then_some(single),
+            Self::Batch(batch) => batch.find_by_id(id),
+        }
+    }
+
+    /// Find the response with the given ID, mutably.
+    ///
+    /// See [`find_by_id`](Self::find_by_id) for more details.
+    pub fn find_by_id_mut(&mut self, id: &Id) -> Option<&mut Response<Payload, ErrData>> {
+        match self {
+            Self::Single(single) => (single.id == *id).then_some(single),
+            Self::Batch(batch) => batch.find_by_id_mut(id),
+        }
+    }
+
     /// Find responses by a list of IDs.
     ///
     /// This is intended to be used in conjunction with
@@ -402,3 +683,120 @@ impl<'a, Payload, ErrData> Iterator for ResponsePacketErrorsIter<'a, Payload, Er
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(id: u64) -> SerializedRequest {
+        Request::new("eth_blockNumber", Id::Number(id), ()).serialize().unwrap()
+    }
+
+    #[test]
+    fn batch_size() {
+        let requests: Vec<_> =
+            (0..3).map(|id| Request::new("eth_blockNumber", Id::Number(id), ())).collect();
+        assert_eq!(validate_batch_size(&requests, 3), Ok(()));
+        assert_eq!(
+            validate_batch_size(&requests, 2),
+            Err(BatchTooLargeError { actual: 3, max: 2 })
+        );
+
+        let packet: RequestPacket = (0..5).map(req).collect();
+        let chunks: Vec<_> = packet.chunk(2).map(|chunk| chunk.len()).collect();
+        assert_eq!(chunks, [2, 2, 1]);
+
+        let single = RequestPacket::Single(req(0));
+        assert!(single.chunk(2).all(|chunk| chunk.as_single().is_some()));
+    }
+
+    fn ids(packet: &RequestPacket) -> Vec<Id> {
+        packet.requests().iter().map(|req| req.id().clone()).collect()
+    }
+
+    #[test]
+    fn split_at_and_merge() {
+        let packet: RequestPacket = (0..3).map(req).collect();
+        for n in 0..=4 {
+            let (first, second) = packet.clone().split_at(n);
+            assert_eq!(first.len(), n.min(3));
+            assert_eq!(second.len(), 3 - n.min(3));
+            assert_eq!(ids(&first.merge(second)), ids(&packet));
+        }
+
+        let (first, second) = packet.clone().split_at(0);
+        assert!(first.is_empty() && first.as_batch().is_some());
+        assert_eq!(ids(&second), ids(&packet));
+        let (first, second) = packet.clone().split_at(3);
+        assert_eq!(ids(&first), ids(&packet));
+        assert!(second.is_empty() && second.as_batch().is_some());
+
+        // A single request splits and merges into batches.
+        let (first, second) = RequestPacket::Single(req(0)).split_at(1);
+        assert_eq!(first.as_batch().map(<[_]>::len), Some(1));
+        assert!(second.is_empty());
+        let merged = RequestPacket::Single(req(0)).merge(RequestPacket::Single(req(1)));
+        assert_eq!(ids(&merged), [Id::Number(0), Id::Number(1)]);
+        assert!(merged.as_batch().is_some());
+    }
+
+    fn resp(id: u64, result: &str) -> Response {
+        let payload = ResponsePayload::Success(serde_json::value::to_raw_value(result).unwrap());
+        Response { id: Id::Number(id), payload }
+    }
+
+    fn result(resp: &Response) -> &str {
+        resp.payload.as_success().unwrap().get()
+    }
+
+    #[test]
+    fn find_by_id() {
+        let mut packet = ResponsePacket::from(vec![resp(0, "a"), resp(1, "b"), resp(0, "c")]);
+
+        // Duplicate IDs resolve to the first response.
+        assert_eq!(packet.find_by_id(&Id::Number(0)).map(result), Some(r#""a""#));
+        assert_eq!(packet.find_by_id(&Id::Number(1)).map(result), Some(r#""b""#));
+        assert!(packet.find_by_id(&Id::Number(2)).is_none());
+        assert!(packet.find_by_id(&Id::None).is_none());
+
+        let found = packet.find_by_id_mut(&Id::Number(0)).unwrap();
+        found.id = Id::Number(2);
+        assert_eq!(packet.find_by_id(&Id::Number(0)).map(result), Some(r#""c""#));
+        assert_eq!(packet.find_by_id(&Id::Number(2)).map(result), Some(r#""a""#));
+        assert!(packet.find_by_id_mut(&Id::Number(3)).is_none());
+
+        for resp in packet.responses_mut() {
+            resp.id = Id::Number(7);
+        }
+        assert_eq!(packet.response_ids().collect::<Vec<_>>(), [&Id::Number(7); 3]);
+
+        let mut single = ResponsePacket::Single(resp(0, "a"));
+        assert_eq!(single.responses_mut().len(), 1);
+        assert!(single.find_by_id_mut(&Id::Number(0)).is_some());
+        assert!(single.find_by_id(&Id::Number(1)).is_none());
+    }
+
+    #[test]
+    fn find_by_id_indexed() {
+        let n = INDEX_THRESHOLD as u64 * 2;
+        let mut responses: Vec<_> = (0..n).map(|id| resp(id, &id.to_string())).collect();
+        responses.push(resp(0, "dup"));
+        let mut packet = ResponsePacket::from(responses);
+        let ResponsePacket::Batch(batch) = &packet else { unreachable!() };
+
+        assert_eq!(packet.find_by_id(&Id::Number(0)).map(result), Some(r#""0""#));
+        let last = format!("\"{}\"", n - 1);
+        assert_eq!(packet.find_by_id(&Id::Number(n - 1)).map(result), Some(last.as_str()));
+        assert!(packet.find_by_id(&Id::Number(n)).is_none());
+        assert!(batch.index.get().is_some());
+
+        // Mutable access drops the index, so that it's rebuilt with the new IDs.
+        packet.find_by_id_mut(&Id::Number(0)).unwrap().id = Id::Number(n);
+        assert_eq!(packet.find_by_id(&Id::Number(0)).map(result), Some(r#""dup""#));
+        assert_eq!(packet.find_by_id(&Id::Number(n)).map(result), Some(r#""0""#));
+
+        packet.responses_mut()[1].id = Id::None;
+        assert!(packet.find_by_id(&Id::Number(1)).is_none());
+        assert_eq!(packet.find_by_id(&Id::None).map(result), Some(r#""1""#));
+    }
+}
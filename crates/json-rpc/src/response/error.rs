@@ -34,6 +34,17 @@ pub struct ErrorPayload<ErrData = Box<RawValue>> {
 }
 
 impl<E> ErrorPayload<E> {
+    /// Create a new error payload with the given code and message.
+    pub fn new(code: i64, message: impl Into<Cow<'static, str>>) -> Self {
+        Self { code, message: message.into(), data: None }
+    }
+
+    /// Sets the error message.
+    pub fn with_message(mut self, message: impl Into<Cow<'static, str>>) -> Self {
+        self.message = message.into();
+        self
+    }
+
     /// Create a new error payload for a parse error.
     pub const fn parse_error() -> Self {
         Self { code: -32700, message: Cow::Borrowed("Parse error"), data: None }
@@ -165,6 +176,16 @@ where
     }
 }
 
+impl ErrorPayload {
+    /// Sets the error data, serializing it into a [`RawValue`].
+    ///
+    /// This is useful when constructing error responses in server code.
+    pub fn with_data<T: Serialize>(mut self, data: T) -> serde_json::Result<Self> {
+        self.data = Some(to_raw_value(&data)?);
+        Ok(self)
+    }
+}
+
 /// Recursively traverses the value, looking for hex data that it can extract.
 ///
 /// Inspired by ethers-js logic:
@@ -189,6 +210,8 @@ impl<ErrData: fmt::Display> fmt::Display for ErrorPayload<ErrData> {
     }
 }
 
+impl<ErrData: fmt::Debug + fmt::Display> std::error::Error for ErrorPayload<ErrData> {}
+
 /// A [`ErrorPayload`] that has been partially deserialized, borrowing its
 /// contents from the deserializer. This is used primarily for intermediate
 /// deserialization. Most users will not require it.
@@ -381,6 +404,25 @@ mod test {
     use super::BorrowedErrorPayload;
     use crate::ErrorPayload;
 
+    #[test]
+    fn builders() {
+        let payload = ErrorPayload::new(-32000, "execution reverted")
+            .with_message(format!("execution reverted: {}", "nope"))
+            .with_data(serde_json::json!({ "reason": "nope" }))
+            .unwrap();
+
+        assert_eq!(payload.code, -32000);
+        assert_eq!(payload.message, "execution reverted: nope");
+        assert_eq!(payload.data.unwrap().get(), r#"{"reason":"nope"}"#);
+    }
+
+    #[test]
+    fn boxed_error() {
+        let payload: ErrorPayload = ErrorPayload::new(-32000, "execution reverted");
+        let err: Box<dyn std::error::Error> = payload.into();
+        assert_eq!(err.to_string(), "error code -32000: execution reverted");
+    }
+
     #[test]
     fn smooth_borrowing() {
         let json = r#"{ "code": -32000, "message": "b", "data": null }"#;
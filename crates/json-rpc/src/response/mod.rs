@@ -127,6 +127,49 @@ impl<Payload, ErrData> Response<Payload, ErrData> {
     pub fn error_code(&self) -> Option<i64> {
         self.payload().error_code()
     }
+
+    /// Converts this response into its [`Id`] and a [`Result`] returning the
+    /// [`ErrorPayload`] as `Err`.
+    ///
+    /// This is useful for correlating batch responses with their requests.
+    pub fn into_result_with_id(self) -> (Id, Result<Payload, ErrorPayload<ErrData>>) {
+        (self.id, self.payload.try_into_success())
+    }
+
+    /// Returns a reference to the [`Id`] of this response and a [`Result`]
+    /// referencing either the successful payload or the [`ErrorPayload`].
+    pub const fn as_result_with_id(&self) -> (&Id, Result<&Payload, &ErrorPayload<ErrData>>) {
+        let result = match &self.payload {
+            ResponsePayload::Success(payload) => Ok(payload),
+            ResponsePayload::Failure(error) => Err(error),
+        };
+        (&self.id, result)
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl<Payload, ErrData> Response<Payload, ErrData> {
+    /// Create a successful response from an SSZ-encoded payload, e.g. the body
+    /// of a Beacon API response with the `application/octet-stream` content
+    /// type.
+    ///
+    /// SSZ responses carry no ID, so the ID of the request must be provided.
+    pub fn from_ssz_bytes(id: Id, bytes: &[u8]) -> Result<Self, ssz::DecodeError>
+    where
+        Payload: ssz::Decode,
+    {
+        Payload::from_ssz_bytes(bytes)
+            .map(|payload| Self { id, payload: ResponsePayload::Success(payload) })
+    }
+
+    /// SSZ-encode the payload of this response, or return `None` if the
+    /// response is an error.
+    pub fn payload_as_ssz_bytes(&self) -> Option<Vec<u8>>
+    where
+        Payload: ssz::Encode,
+    {
+        self.payload.as_success().map(ssz::Encode::as_ssz_bytes)
+    }
 }
 
 impl<Payload, ErrData> Response<Payload, ErrData>
@@ -362,6 +405,40 @@ mod test {
         assert!(matches!(response.payload, super::ResponsePayload::Failure(_)));
     }
 
+    #[test]
+    fn result_with_id() {
+        let response = r#"{"jsonrpc":"2.0","result":"0x1","id":7}"#;
+        let response: super::Response = serde_json::from_str(response).unwrap();
+        let (id, result) = response.as_result_with_id();
+        assert_eq!(id, &super::Id::Number(7));
+        assert_eq!(result.unwrap().get(), r#""0x1""#);
+
+        let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"boom"},"id":8}"#;
+        let response: super::Response = serde_json::from_str(response).unwrap();
+        let (id, result) = response.into_result_with_id();
+        assert_eq!(id, super::Id::Number(8));
+        assert_eq!(result.unwrap_err().message, "boom");
+    }
+
+    #[test]
+    #[cfg(feature = "ssz")]
+    fn ssz_roundtrip() {
+        let response = super::Response::<Vec<u64>>::from_ssz_bytes(
+            super::Id::Number(1),
+            &ssz::Encode::as_ssz_bytes(&vec![1u64, 2]),
+        )
+        .unwrap();
+        assert_eq!(response.payload.as_success(), Some(&vec![1, 2]));
+        assert_eq!(
+            response.payload_as_ssz_bytes(),
+            Some(ssz::Encode::as_ssz_bytes(&vec![1u64, 2]))
+        );
+
+        let response = super::Response::<Vec<u64>>::method_not_found(super::Id::Number(2));
+        assert_eq!(response.payload_as_ssz_bytes(), None);
+        assert!(super::Response::<u64>::from_ssz_bytes(super::Id::None, &[1]).is_err());
+    }
+
     #[test]
     fn serde_unknown() {
         let response = r#"{
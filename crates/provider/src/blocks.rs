@@ -249,7 +249,7 @@ mod tests {
         yield_block(false).await;
     }
     #[tokio::test]
-    #[cfg(feature = "ws")]
+    #[cfg(feature = "__ws")]
     async fn yield_block_ws() {
         yield_block(true).await;
     }
@@ -277,7 +277,7 @@ mod tests {
         yield_many_blocks(false).await;
     }
     #[tokio::test]
-    #[cfg(feature = "ws")]
+    #[cfg(feature = "__ws")]
     async fn yield_many_blocks_ws() {
         yield_many_blocks(true).await;
     }
@@ -465,7 +465,7 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
     }
 
     /// Build this provider with a websocket connection.
-    #[cfg(feature = "ws")]
+    #[cfg(feature = "__ws")]
     pub async fn connect_ws(
         self,
         connect: alloy_transport_ws::WsConnect,
@@ -480,7 +480,7 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
     }
 
     /// Build this provider with a websocket connection.
-    #[cfg(feature = "ws")]
+    #[cfg(feature = "__ws")]
     #[deprecated(since = "0.12.6", note = "use `connect_ws` instead")]
     pub async fn on_ws(
         self,
@@ -0,0 +1,86 @@
+//! This module extends the Ethereum JSON-RPC provider with chain measurement helpers.
+use crate::Provider;
+use alloy_consensus::BlockHeader;
+use alloy_network::Network;
+use alloy_network_primitives::BlockResponse;
+use alloy_rpc_types_eth::BlockNumberOrTag;
+use alloy_transport::{TransportErrorKind, TransportResult};
+use std::time::Duration;
+
+/// Extension helpers built on top of the standard [`Provider`] methods.
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+pub trait ProviderExt<N>: Send + Sync {
+    /// Measures the average block time of the connected chain.
+    ///
+    /// The average is computed over the `sample_count` most recent block headers, i.e. over
+    /// `sample_count - 1` block intervals. Only the oldest and latest headers of the sample are
+    /// fetched, as the intermediate timestamps cancel out of the average.
+    ///
+    /// If the chain is shorter than `sample_count` blocks, the sample starts at genesis.
+    ///
+    /// This is useful for picking a polling interval that matches the chain's actual cadence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `sample_count` is less than 2, if the chain has no blocks past
+    /// genesis, or if any of the headers can't be fetched.
+    async fn measure_block_time(&self, sample_count: u32) -> TransportResult<Duration>;
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl<N, P> ProviderExt<N> for P
+where
+    N: Network,
+    P: Provider<N>,
+{
+    async fn measure_block_time(&self, sample_count: u32) -> TransportResult<Duration> {
+        if sample_count < 2 {
+            return Err(TransportErrorKind::custom_str(
+                "at least 2 blocks are required to measure block time",
+            ));
+        }
+
+        let latest = self.get_block_number().await?;
+        let oldest = latest.saturating_sub(sample_count as u64 - 1);
+        if oldest == latest {
+            return Err(TransportErrorKind::custom_str("not enough blocks to measure block time"));
+        }
+
+        let timestamp = async |number| -> TransportResult<u64> {
+            self.get_block_by_number(BlockNumberOrTag::Number(number))
+                .await?
+                .map(|block| block.header().timestamp())
+                .ok_or_else(|| TransportErrorKind::custom_str(&format!("block {number} not found")))
+        };
+        let (oldest_ts, latest_ts) = futures::try_join!(timestamp(oldest), timestamp(latest))?;
+
+        let elapsed = Duration::from_secs(latest_ts.saturating_sub(oldest_ts));
+        Ok(elapsed / (latest - oldest) as u32)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ProviderBuilder;
+
+    #[tokio::test]
+    async fn measure_block_time_requires_two_samples() {
+        let provider = ProviderBuilder::new().connect_http("http://localhost:1".parse().unwrap());
+        assert!(provider.measure_block_time(1).await.is_err());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "anvil-node")]
+    async fn measure_block_time_anvil() {
+        use crate::ext::AnvilApi;
+
+        let provider = ProviderBuilder::new().connect_anvil();
+        provider.anvil_mine(Some(10), Some(12)).await.unwrap();
+
+        let block_time = provider.measure_block_time(5).await.unwrap();
+        assert_eq!(block_time, Duration::from_secs(12));
+    }
+}
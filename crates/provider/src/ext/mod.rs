@@ -1,5 +1,8 @@
 //! Extended APIs for the provider module.
 
+mod block_time;
+pub use block_time::ProviderExt;
+
 #[cfg(feature = "admin-api")]
 mod admin;
 #[cfg(feature = "admin-api")]
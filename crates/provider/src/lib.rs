@@ -53,10 +53,10 @@ pub use alloy_network::{self as network, Network};
 
 pub use alloy_transport as transport;
 
-#[cfg(feature = "ws")]
+#[cfg(feature = "__ws")]
 pub use alloy_rpc_client::WsConnect;
 
-#[cfg(all(feature = "ws", not(target_family = "wasm")))]
+#[cfg(all(feature = "__ws", not(target_family = "wasm")))]
 pub use alloy_rpc_client::WebSocketConfig;
 
 #[cfg(feature = "ipc")]
@@ -1645,7 +1645,7 @@ mod tests {
         assert_eq!(0, num);
     }
 
-    #[cfg(feature = "ws")]
+    #[cfg(feature = "__ws")]
     #[tokio::test]
     async fn subscribe_blocks_http() {
         let provider = ProviderBuilder::new().connect_anvil_with_config(|a| a.block_time(1));
@@ -1660,7 +1660,7 @@ mod tests {
     }
 
     // Ensures we can connect to a websocket using `wss`.
-    #[cfg(feature = "ws")]
+    #[cfg(feature = "__ws")]
     #[tokio::test]
     async fn websocket_tls_setup() {
         for url in ["wss://mainnet.infura.io/ws/v3/b0f825787ba840af81e46c6a64d20754"] {
@@ -1668,7 +1668,7 @@ mod tests {
         }
     }
 
-    #[cfg(feature = "ws")]
+    #[cfg(feature = "__ws")]
     #[tokio::test]
     async fn subscribe_blocks_ws() {
         use futures::stream::StreamExt;
@@ -1691,7 +1691,7 @@ mod tests {
         }
     }
 
-    #[cfg(feature = "ws")]
+    #[cfg(feature = "__ws")]
     #[tokio::test]
     async fn subscribe_full_blocks() {
         use futures::StreamExt;
@@ -1718,7 +1718,7 @@ mod tests {
     }
 
     #[tokio::test]
-    #[cfg(feature = "ws")]
+    #[cfg(feature = "__ws")]
     async fn subscribe_blocks_ws_remote() {
         use futures::stream::StreamExt;
 
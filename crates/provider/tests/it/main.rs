@@ -3,5 +3,5 @@
 
 mod mock;
 
-#[cfg(feature = "ws")]
+#[cfg(feature = "__ws")]
 mod ws;
@@ -27,6 +27,14 @@ pub trait PubSubConnect: Sized + Send + Sync + 'static {
         self.connect()
     }
 
+    /// Called once the service shuts down, with the result it shut down with.
+    ///
+    /// The result is `Ok` if the frontend was dropped, or the error that made
+    /// the service give up, e.g. after reconnecting failed too many times.
+    fn on_shutdown(&self, result: &TransportResult<()>) {
+        let _ = result;
+    }
+
     /// Convert the configuration object into a service with a running backend.
     fn into_service(self) -> impl_future!(<Output = TransportResult<PubSubFrontend>>) {
         PubSubService::connect(self)
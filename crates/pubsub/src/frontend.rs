@@ -1,17 +1,122 @@
-use crate::{ix::PubSubInstruction, managers::InFlight, RawSubscription};
-use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest};
+use crate::{
+    ix::PubSubInstruction,
+    managers::InFlight,
+    notification::{NotificationStream, RawNotification, NOTIFICATION_CHANNEL_SIZE},
+    RawSubscription, Subscription,
+};
+use alloy_json_rpc::{
+    Id, Request, RequestPacket, Response, ResponsePacket, ResponsePayload, ResponseSizeLimit,
+    RpcError, SerializedRequest,
+};
 use alloy_primitives::B256;
 use alloy_transport::{TransportError, TransportErrorKind, TransportFut, TransportResult};
-use futures::{future::try_join_all, FutureExt, TryFutureExt};
+use futures::{
+    future::{join_all, try_join_all},
+    FutureExt, TryFutureExt,
+};
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::value::{to_raw_value, RawValue};
 use std::{
+    borrow::Cow,
+    fmt,
     future::Future,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot, Notify};
+
+/// Counter used to give batched subscription requests unique IDs, so that
+/// they can't collide with the IDs of requests issued by the client.
+static BATCH_SUB_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A single `eth_subscribe` request, for use with
+/// [`PubSubFrontend::batch_subscribe`].
+#[derive(Clone, Debug)]
+pub struct SubscribeRequest {
+    sub_type: Cow<'static, str>,
+    params: Option<Box<RawValue>>,
+}
+
+impl SubscribeRequest {
+    /// Create a new subscribe request for the given subscription type, e.g.
+    /// `"newHeads"` or `"logs"`.
+    pub fn new(sub_type: impl Into<Cow<'static, str>>) -> Self {
+        Self { sub_type: sub_type.into(), params: None }
+    }
+
+    /// Set the filter parameters of the subscription.
+    ///
+    /// These are sent as the second element of the `eth_subscribe` params.
+    pub fn with_params<T: Serialize>(mut self, params: T) -> serde_json::Result<Self> {
+        self.params = Some(to_raw_value(&params)?);
+        Ok(self)
+    }
+
+    /// Get the subscription type.
+    pub fn sub_type(&self) -> &str {
+        &self.sub_type
+    }
+
+    /// Get the serialized filter parameters, if any.
+    pub fn params(&self) -> Option<&RawValue> {
+        self.params.as_deref()
+    }
+
+    /// Serialize into an `eth_subscribe` request with the given ID.
+    fn serialize(self, id: Id) -> serde_json::Result<SerializedRequest> {
+        match self.params {
+            Some(params) => Request::new("eth_subscribe", id, (self.sub_type, params)).serialize(),
+            None => Request::new("eth_subscribe", id, (self.sub_type,)).serialize(),
+        }
+    }
+}
+
+/// A callback invoked after the connection was re-established.
+type ReconnectHook = Arc<dyn Fn() + Send + Sync>;
+
+/// The callbacks registered with [`PubSubFrontend::on_reconnect`], shared by
+/// the frontend and the service.
+#[derive(Clone, Default)]
+pub(crate) struct ReconnectHooks {
+    hooks: Arc<RwLock<Vec<ReconnectHook>>>,
+    /// Wakes up [`PubSubFrontend::wait_for_reconnect`].
+    signal: Arc<ReconnectSignal>,
+}
+
+/// Signals reconnections and the shutdown of the service.
+#[derive(Debug, Default)]
+struct ReconnectSignal {
+    notify: Notify,
+    shut_down: AtomicBool,
+}
+
+impl fmt::Debug for ReconnectHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReconnectHooks").field("len", &self.hooks.read().len()).finish()
+    }
+}
+
+impl ReconnectHooks {
+    /// Invoke all registered callbacks, in registration order.
+    pub(crate) fn run(&self) {
+        // Clone the hooks so that callbacks may register further hooks.
+        let hooks = self.hooks.read().clone();
+        for hook in hooks {
+            hook();
+        }
+        self.signal.notify.notify_waiters();
+    }
+
+    /// Wake up the tasks waiting for a reconnection, as the service shut down.
+    pub(crate) fn shutdown(&self) {
+        self.signal.shut_down.store(true, Ordering::Release);
+        self.signal.notify.notify_waiters();
+    }
+}
 
 /// A `PubSubFrontend` is [`Transport`] composed of a channel to a running
 /// PubSub service.
@@ -23,12 +128,111 @@ pub struct PubSubFrontend {
     /// The number of items to buffer in new subscription channels. Defaults to
     /// 16. See [`tokio::sync::broadcast::channel`] for a description.
     channel_size: Arc<AtomicUsize>,
+    /// Incoming notifications that are not routed to any subscription.
+    notifications: broadcast::Sender<RawNotification>,
+    /// The maximum number of concurrent subscriptions, shared with the
+    /// service. Zero means no limit.
+    subscription_limit: Arc<AtomicUsize>,
+    /// The number of reconnections of the connection, shared with the
+    /// connection handles.
+    reconnect_count: Arc<AtomicU32>,
+    /// Callbacks invoked after reconnecting, shared with the service.
+    reconnect_hooks: ReconnectHooks,
 }
 
 impl PubSubFrontend {
     /// Create a new frontend.
     pub fn new(tx: mpsc::UnboundedSender<PubSubInstruction>) -> Self {
-        Self { tx, channel_size: Arc::new(AtomicUsize::new(16)) }
+        Self {
+            tx,
+            channel_size: Arc::new(AtomicUsize::new(16)),
+            notifications: broadcast::channel(NOTIFICATION_CHANNEL_SIZE).0,
+            subscription_limit: Arc::new(AtomicUsize::new(0)),
+            reconnect_count: Arc::new(AtomicU32::new(0)),
+            reconnect_hooks: ReconnectHooks::default(),
+        }
+    }
+
+    /// Get the reconnection counter, shared with the connection handles.
+    pub(crate) fn reconnect_count_handle(&self) -> Arc<AtomicU32> {
+        self.reconnect_count.clone()
+    }
+
+    /// Returns the number of times the service re-established the connection
+    /// since it was created.
+    ///
+    /// See [`ConnectionHandle::reconnect_count`](crate::ConnectionHandle::reconnect_count).
+    pub fn reconnect_count(&self) -> u32 {
+        self.reconnect_count.load(Ordering::Relaxed)
+    }
+
+    /// Get the reconnection callbacks, shared with the service.
+    pub(crate) fn reconnect_hooks_handle(&self) -> ReconnectHooks {
+        self.reconnect_hooks.clone()
+    }
+
+    /// Register a callback that is invoked every time the service
+    /// re-established the connection, after pending requests were re-issued.
+    ///
+    /// Multiple callbacks may be registered, and are invoked in registration
+    /// order. Callbacks are shared by all clones of this frontend, and run on
+    /// the service task, so they should not block.
+    pub fn on_reconnect(&self, hook: impl Fn() + Send + Sync + 'static) {
+        self.reconnect_hooks.hooks.write().push(Arc::new(hook));
+    }
+
+    /// Wait until the service re-established the connection more than `since`
+    /// times, as counted by [`reconnect_count`](Self::reconnect_count).
+    ///
+    /// Resolves to `false` if the service shuts down first. The returned
+    /// future does not keep the service alive.
+    pub fn wait_for_reconnect(&self, since: u32) -> impl Future<Output = bool> + Send + 'static {
+        let count = self.reconnect_count.clone();
+        let signal = self.reconnect_hooks.signal.clone();
+        async move {
+            loop {
+                // Register for the notification before checking, so that a
+                // notification in between is not missed.
+                let notified = signal.notify.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                if count.load(Ordering::Relaxed) > since {
+                    return true;
+                }
+                if signal.shut_down.load(Ordering::Acquire) {
+                    return false;
+                }
+                notified.await;
+            }
+        }
+    }
+
+    /// Get the subscription limit, shared with the service.
+    pub(crate) fn subscription_limit_handle(&self) -> Arc<AtomicUsize> {
+        self.subscription_limit.clone()
+    }
+
+    /// Get the sender for unmatched notifications, shared with the service.
+    pub(crate) fn notifications_tx(&self) -> broadcast::Sender<RawNotification> {
+        self.notifications.clone()
+    }
+
+    /// Subscribe to incoming notifications that are not routed to any
+    /// subscription.
+    ///
+    /// Each receiver gets every notification received after it was created.
+    /// See [`RawNotification`] for details.
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<RawNotification> {
+        self.notifications.subscribe()
+    }
+
+    /// Get a stream of incoming notifications that are not routed to any
+    /// subscription.
+    ///
+    /// See [`subscribe_notifications`](Self::subscribe_notifications) for
+    /// multi-consumer use.
+    pub fn notification_stream(&self) -> NotificationStream {
+        NotificationStream::new(self.clone())
     }
 
     /// Get the subscription ID for a local ID.
@@ -56,18 +260,30 @@ impl PubSubFrontend {
     }
 
     /// Send a request.
+    ///
+    /// If the request has a [`ResponseSizeLimit`], a larger result fails with
+    /// a `"response too large"` error. As messages are received in full, the
+    /// connection should also limit their size, e.g. via
+    /// `WsConnect::with_message_size_limit`.
     pub fn send(
         &self,
         req: SerializedRequest,
     ) -> impl Future<Output = TransportResult<Response>> + Send + 'static {
         let tx = self.tx.clone();
         let channel_size = self.channel_size.load(Ordering::Relaxed);
+        let limit = req.meta().extensions().get::<ResponseSizeLimit>().map(|limit| limit.0);
 
         async move {
             let (in_flight, rx) = InFlight::new(req, channel_size);
             tx.send(PubSubInstruction::Request(in_flight))
                 .map_err(|_| TransportErrorKind::backend_gone())?;
-            rx.await.map_err(|_| TransportErrorKind::backend_gone())?
+            let resp = rx.await.map_err(|_| TransportErrorKind::backend_gone())??;
+            match (&resp.payload, limit) {
+                (ResponsePayload::Success(val), Some(limit)) if val.get().len() > limit => {
+                    Err(TransportErrorKind::custom_str("response too large"))
+                }
+                _ => Ok(resp),
+            }
         }
     }
 
@@ -78,11 +294,66 @@ impl PubSubFrontend {
         match req {
             RequestPacket::Single(req) => self.send(req).map_ok(ResponsePacket::Single).boxed(),
             RequestPacket::Batch(reqs) => try_join_all(reqs.into_iter().map(|req| self.send(req)))
-                .map_ok(ResponsePacket::Batch)
+                .map_ok(|responses| ResponsePacket::Batch(responses.into()))
                 .boxed(),
         }
     }
 
+    /// Subscribe to several subscriptions at once.
+    ///
+    /// All `eth_subscribe` requests are sent to the server in a single JSON
+    /// batch, so that no notification is missed between two subscriptions,
+    /// e.g. when subscribing to both `newHeads` and `logs`.
+    ///
+    /// The returned subscriptions are in the same order as the requests.
+    /// Errors are reported per subscription, so some subscriptions may
+    /// succeed while others fail.
+    pub fn batch_subscribe(
+        &self,
+        requests: Vec<SubscribeRequest>,
+    ) -> impl Future<Output = Vec<TransportResult<Subscription<Box<RawValue>>>>> + Send + 'static
+    {
+        let this = self.clone();
+        let channel_size = self.channel_size.load(Ordering::Relaxed);
+
+        async move {
+            let mut in_flights = Vec::with_capacity(requests.len());
+            let waiters = requests
+                .into_iter()
+                .map(|req| {
+                    let id = BATCH_SUB_ID.fetch_add(1, Ordering::Relaxed);
+                    let req = req
+                        .serialize(Id::String(format!("alloy-pubsub-batch-{id}")))
+                        .map_err(RpcError::ser_err)?;
+                    let (in_flight, rx) = InFlight::new(req, channel_size);
+                    in_flights.push(in_flight);
+                    Ok(rx)
+                })
+                .collect::<Vec<TransportResult<_>>>();
+
+            let sent =
+                in_flights.is_empty() || this.tx.send(PubSubInstruction::Batch(in_flights)).is_ok();
+
+            join_all(waiters.into_iter().map(|waiter| {
+                let this = this.clone();
+                async move {
+                    let rx = waiter?;
+                    if !sent {
+                        return Err(TransportErrorKind::backend_gone());
+                    }
+                    let resp = rx.await.map_err(|_| TransportErrorKind::backend_gone())??;
+                    let local_id = match resp.payload {
+                        ResponsePayload::Success(val) => serde_json::from_str::<B256>(val.get())
+                            .map_err(|err| RpcError::deser_err(err, val.get()))?,
+                        ResponsePayload::Failure(err) => return Err(RpcError::ErrorResp(err)),
+                    };
+                    this.get_subscription(local_id).await.map(Subscription::from)
+                }
+            }))
+            .await
+        }
+    }
+
     /// Get the currently configured channel size. This is the number of items
     /// to buffer in new subscription channels. Defaults to 16. See
     /// [`tokio::sync::broadcast`] for a description of relevant
@@ -99,6 +370,37 @@ impl PubSubFrontend {
         debug_assert_ne!(channel_size, 0, "channel size must be non-zero");
         self.channel_size.store(channel_size, Ordering::Relaxed);
     }
+
+    /// Limit the number of concurrent subscriptions on the connection.
+    ///
+    /// Once `n` subscriptions are active or pending, further `eth_subscribe`
+    /// requests fail with a "subscription limit reached" error instead of
+    /// being sent to the server. Subscribing again with the exact same
+    /// parameters as an active subscription is always allowed, as it reuses
+    /// that subscription.
+    ///
+    /// The limit is shared by all clones of this frontend. See
+    /// [`set_subscription_limit`](Self::set_subscription_limit).
+    pub fn with_subscription_limit(self, n: usize) -> Self {
+        self.set_subscription_limit(Some(n));
+        self
+    }
+
+    /// Get the currently configured subscription limit, if any.
+    pub fn subscription_limit(&self) -> Option<usize> {
+        match self.subscription_limit.load(Ordering::Relaxed) {
+            0 => None,
+            n => Some(n),
+        }
+    }
+
+    /// Set or remove the subscription limit. Default is no limit.
+    ///
+    /// See [`with_subscription_limit`](Self::with_subscription_limit).
+    pub fn set_subscription_limit(&self, limit: Option<usize>) {
+        debug_assert_ne!(limit, Some(0), "subscription limit must be non-zero");
+        self.subscription_limit.store(limit.unwrap_or_default(), Ordering::Relaxed);
+    }
 }
 
 impl tower::Service<RequestPacket> for PubSubFrontend {
@@ -1,5 +1,9 @@
 use alloy_json_rpc::PubSubItem;
 use serde_json::value::RawValue;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
 use tokio::{
     sync::{
         mpsc,
@@ -30,9 +34,19 @@ pub struct ConnectionHandle {
     /// Max number of retries before failing and exiting the connection.
     /// Default is 10.
     pub(crate) max_retries: u32,
-    /// The interval between retries.
+    /// The initial interval between retries.
     /// Default is 3 seconds.
     pub(crate) retry_interval: Duration,
+    /// The factor by which the retry interval grows after each failed attempt.
+    /// Default is 2.0.
+    pub(crate) retry_backoff_factor: f64,
+    /// The upper bound for the interval between retries.
+    /// Default is 30 seconds.
+    pub(crate) retry_max_interval: Duration,
+
+    /// The number of reconnections since the connection was created. Shared
+    /// by all handles of the connection.
+    pub(crate) reconnect_count: Arc<AtomicU32>,
 }
 
 impl ConnectionHandle {
@@ -50,6 +64,9 @@ impl ConnectionHandle {
             shutdown: shutdown_tx,
             max_retries: 10,
             retry_interval: Duration::from_secs(3),
+            retry_backoff_factor: 2.0,
+            retry_max_interval: Duration::from_secs(30),
+            reconnect_count: Default::default(),
         };
         let interface = ConnectionInterface {
             from_frontend,
@@ -67,12 +84,44 @@ impl ConnectionHandle {
         self
     }
 
-    /// Set the interval between retries.
+    /// Set the initial interval between retries.
     pub const fn with_retry_interval(mut self, retry_interval: Duration) -> Self {
         self.retry_interval = retry_interval;
         self
     }
 
+    /// Set the factor by which the retry interval grows after each failed
+    /// attempt. A factor of `1.0` results in a constant retry interval.
+    /// Default is 2.0.
+    pub const fn with_retry_backoff_factor(mut self, retry_backoff_factor: f64) -> Self {
+        self.retry_backoff_factor = retry_backoff_factor;
+        self
+    }
+
+    /// Set the upper bound for the interval between retries.
+    /// Default is 30 seconds.
+    pub const fn with_retry_max_interval(mut self, retry_max_interval: Duration) -> Self {
+        self.retry_max_interval = retry_max_interval;
+        self
+    }
+
+    /// Returns the number of times the connection was re-established since
+    /// it was created.
+    pub fn reconnect_count(&self) -> u32 {
+        self.reconnect_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the delay to wait before the given (zero-indexed) reconnect
+    /// attempt.
+    ///
+    /// The delay is computed as `min(initial * factor^attempt, max_interval)`.
+    pub(crate) fn retry_delay(&self, attempt: u32) -> Duration {
+        let exp = self.retry_backoff_factor.powi(attempt.min(i32::MAX as u32) as i32);
+        let secs = self.retry_interval.as_secs_f64() * exp;
+        Duration::try_from_secs_f64(secs)
+            .map_or(self.retry_max_interval, |delay| delay.min(self.retry_max_interval))
+    }
+
     /// Shutdown the backend.
     pub fn shutdown(self) {
         let _ = self.shutdown.send(());
@@ -121,3 +170,42 @@ impl ConnectionInterface {
         let _ = self.error.send(());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_schedule() {
+        let (handle, _interface) = ConnectionHandle::new();
+        let handle = handle
+            .with_retry_interval(Duration::from_millis(100))
+            .with_retry_backoff_factor(2.0)
+            .with_retry_max_interval(Duration::from_secs(1));
+
+        let schedule = (0..6).map(|attempt| handle.retry_delay(attempt)).collect::<Vec<_>>();
+        assert_eq!(
+            schedule,
+            [
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400),
+                Duration::from_millis(800),
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+            ]
+        );
+        assert_eq!(handle.retry_delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn retry_delay_constant() {
+        let (handle, _interface) = ConnectionHandle::new();
+        let handle =
+            handle.with_retry_interval(Duration::from_secs(3)).with_retry_backoff_factor(1.0);
+
+        for attempt in 0..10 {
+            assert_eq!(handle.retry_delay(attempt), Duration::from_secs(3));
+        }
+    }
+}
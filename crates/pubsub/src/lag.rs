@@ -0,0 +1,174 @@
+use crate::{Subscription, SubscriptionItem, TimestampedNotification};
+use alloy_primitives::U64;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::value::RawValue;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::{Instant, SystemTime, UNIX_EPOCH};
+
+#[cfg(not(target_family = "wasm"))]
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// An item received by a [`LagMonitoredSubscription`], together with timing
+/// information.
+#[derive(Clone, Debug)]
+pub struct TimestampedItem<T> {
+    /// The received item.
+    pub item: T,
+    /// The UNIX timestamp in seconds carried by the notification, if any.
+    ///
+    /// This is the `timestamp` field of block headers, or the
+    /// `blockTimestamp` field of logs.
+    pub server_timestamp: Option<u64>,
+    /// The time at which the pubsub service received the item from the
+    /// backend.
+    pub receive_time: Instant,
+    /// How far the item lags behind real time, i.e. the time elapsed between
+    /// the server timestamp and the receive time. Zero if the notification
+    /// carries no timestamp.
+    pub lag: Duration,
+}
+
+/// The timestamp fields of a notification.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NotificationTimestamp {
+    timestamp: Option<U64>,
+    block_timestamp: Option<U64>,
+}
+
+impl NotificationTimestamp {
+    fn extract(value: &RawValue) -> Option<u64> {
+        let ts = serde_json::from_str::<Self>(value.get()).ok()?;
+        ts.timestamp.or(ts.block_timestamp).map(|ts| ts.to())
+    }
+}
+
+/// A [`Subscription`] that measures how far its items lag behind real time,
+/// and warns when the lag exceeds a threshold.
+///
+/// Created with [`Subscription::with_lag_monitor`].
+#[derive(Debug)]
+#[must_use]
+pub struct LagMonitoredSubscription<T> {
+    inner: Subscription<T>,
+    threshold: Duration,
+}
+
+impl<T> Subscription<T> {
+    /// Monitor the lag of the subscription items, emitting a warning when it
+    /// exceeds `threshold`.
+    ///
+    /// See [`TimestampedItem`] for how the lag is measured.
+    pub const fn with_lag_monitor(self, threshold: Duration) -> LagMonitoredSubscription<T> {
+        LagMonitoredSubscription { inner: self, threshold }
+    }
+}
+
+impl<T> LagMonitoredSubscription<T> {
+    /// Get the lag threshold above which a warning is emitted.
+    pub const fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    /// Get a reference to the inner subscription.
+    pub const fn inner(&self) -> &Subscription<T> {
+        &self.inner
+    }
+
+    /// Consume the monitor, returning the inner subscription.
+    pub fn into_inner(self) -> Subscription<T> {
+        self.inner
+    }
+}
+
+impl<T: DeserializeOwned> LagMonitoredSubscription<T> {
+    /// Await an item of the expected type from the channel, together with its
+    /// timing information.
+    ///
+    /// Notifications of unexpected types are discarded. See
+    /// [`Subscription::recv`].
+    pub async fn recv(&mut self) -> Result<TimestampedItem<T>, broadcast::error::RecvError> {
+        loop {
+            let TimestampedNotification { value, receive_time } =
+                self.inner.inner.rx.recv().await?;
+            let server_timestamp = NotificationTimestamp::extract(&value);
+
+            let SubscriptionItem::Item(item) = value.into() else { continue };
+
+            // The wall-clock time at which the item was received.
+            let received_at =
+                SystemTime::now().checked_sub(receive_time.elapsed()).unwrap_or(UNIX_EPOCH);
+            let lag = server_timestamp
+                .and_then(|ts| {
+                    received_at.duration_since(UNIX_EPOCH + Duration::from_secs(ts)).ok()
+                })
+                .unwrap_or_default();
+            if lag > self.threshold {
+                warn!(
+                    local_id = %self.inner.local_id(),
+                    ?lag,
+                    threshold = ?self.threshold,
+                    "subscription is lagging behind real time"
+                );
+            }
+
+            return Ok(TimestampedItem { item, server_timestamp, receive_time, lag });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RawSubscription;
+    use alloy_primitives::B256;
+
+    #[tokio::test]
+    async fn lag_from_server_timestamp() {
+        let (tx, rx) = broadcast::channel(4);
+        let sub: Subscription<serde_json::Value> =
+            RawSubscription { rx, local_id: B256::ZERO }.into();
+        let mut sub = sub.with_lag_monitor(Duration::from_secs(60));
+
+        let notification = |value: &str| TimestampedNotification {
+            value: RawValue::from_string(value.into()).unwrap(),
+            receive_time: Instant::now(),
+        };
+
+        tx.send(notification(r#"{"number":"0x1","timestamp":"0x3e8"}"#)).unwrap();
+        let item = sub.recv().await.unwrap();
+        assert_eq!(item.server_timestamp, Some(1000));
+        assert!(item.lag > sub.threshold());
+
+        tx.send(notification("\"0xabc\"")).unwrap();
+        let item = sub.recv().await.unwrap();
+        assert_eq!(item.server_timestamp, None);
+        assert_eq!(item.lag, Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn lag_from_service_receive_time() {
+        let (tx, rx) = broadcast::channel(4);
+        let sub: Subscription<serde_json::Value> =
+            RawSubscription { rx, local_id: B256::ZERO }.into();
+        let mut sub = sub.with_lag_monitor(Duration::from_secs(60));
+
+        // An item received by the service 10s ago, stamped 5s before that.
+        let receive_time = Instant::now() - Duration::from_secs(10);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let value = format!(r#"{{"timestamp":"{:#x}"}}"#, now - 15);
+        tx.send(TimestampedNotification {
+            value: RawValue::from_string(value).unwrap(),
+            receive_time,
+        })
+        .unwrap();
+
+        let item = sub.recv().await.unwrap();
+        assert_eq!(item.receive_time, receive_time);
+        assert!(item.lag >= Duration::from_secs(4), "{:?}", item.lag);
+        assert!(item.lag <= Duration::from_secs(6), "{:?}", item.lag);
+    }
+}
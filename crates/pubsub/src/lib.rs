@@ -13,7 +13,7 @@ mod connect;
 pub use connect::PubSubConnect;
 
 mod frontend;
-pub use frontend::PubSubFrontend;
+pub use frontend::{PubSubFrontend, SubscribeRequest};
 
 mod ix;
 pub use ix::PubSubInstruction;
@@ -21,13 +21,22 @@ pub use ix::PubSubInstruction;
 mod handle;
 pub use handle::{ConnectionHandle, ConnectionInterface};
 
+mod lag;
+pub use lag::{LagMonitoredSubscription, TimestampedItem};
+
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+
 mod managers;
 pub use managers::InFlight;
 
+mod notification;
+pub use notification::{NotificationStream, RawNotification};
+
 mod service;
 
 mod sub;
 pub use sub::{
     RawSubscription, SubAnyStream, SubResultStream, Subscription, SubscriptionItem,
-    SubscriptionStream,
+    SubscriptionStream, TimestampedNotification,
 };
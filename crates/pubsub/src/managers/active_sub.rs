@@ -1,8 +1,7 @@
-use crate::RawSubscription;
+use crate::{RawSubscription, TimestampedNotification};
 use alloy_json_rpc::SerializedRequest;
 use alloy_primitives::B256;
 use parking_lot::Mutex;
-use serde_json::value::RawValue;
 use std::{fmt, hash::Hash, ops::DerefMut};
 use tokio::sync::broadcast;
 
@@ -13,7 +12,7 @@ pub(crate) struct ActiveSubscription {
     /// The serialized subscription request.
     pub(crate) request: SerializedRequest,
     /// The channel via which notifications are broadcast.
-    pub(crate) tx: broadcast::Sender<Box<RawValue>>,
+    pub(crate) tx: broadcast::Sender<TimestampedNotification>,
     /// The initial channel via which notifications are received.
     ///
     /// This is stored so that we don't drop any notifications between initializing
@@ -21,7 +20,7 @@ pub(crate) struct ActiveSubscription {
     ///
     /// This is wrapped in a [`Mutex`] to allow for mutable access to the receiver without making
     /// [`ActiveSubscription::subscribe`] require mutable self.
-    pub(crate) rx: Mutex<Option<broadcast::Receiver<Box<RawValue>>>>,
+    pub(crate) rx: Mutex<Option<broadcast::Receiver<TimestampedNotification>>>,
 }
 
 // NB: We implement this to prevent any incorrect future implementations.
@@ -97,7 +96,7 @@ impl ActiveSubscription {
 
     /// Notify the subscription channel of a new value, if any receiver exists.
     /// If no receiver exists, the notification is dropped.
-    pub(crate) fn notify(&self, notification: Box<RawValue>) {
+    pub(crate) fn notify(&self, notification: TimestampedNotification) {
         if self.tx.receiver_count() > 0 {
             let _ = self.tx.send(notification);
         }
@@ -1,8 +1,14 @@
-use crate::{managers::ActiveSubscription, RawSubscription};
+use crate::{managers::ActiveSubscription, RawSubscription, TimestampedNotification};
 use alloy_json_rpc::{EthNotification, SerializedRequest, SubId};
 use alloy_primitives::B256;
 use bimap::BiBTreeMap;
 
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::Instant;
+
+#[cfg(not(target_family = "wasm"))]
+use std::time::Instant;
+
 #[derive(Debug, Default)]
 pub(crate) struct SubscriptionManager {
     /// The subscriptions.
@@ -22,6 +28,11 @@ impl SubscriptionManager {
         self.local_to_sub.len()
     }
 
+    /// Returns `true` if a subscription with the given local id exists.
+    pub(crate) fn contains(&self, local_id: &B256) -> bool {
+        self.local_to_sub.contains_left(local_id)
+    }
+
     /// Insert a subscription.
     fn insert(
         &mut self,
@@ -85,14 +96,22 @@ impl SubscriptionManager {
     }
 
     /// Notify the subscription channel of a new value, if the sub is known,
-    /// and if any receiver exists. If the sub id is unknown, or no receiver
-    /// exists, the notification is dropped.
-    pub(crate) fn notify(&mut self, notification: EthNotification) {
+    /// and if any receiver exists. If no receiver exists, the notification is
+    /// dropped.
+    ///
+    /// If the sub id is unknown, the notification is returned.
+    pub(crate) fn notify(
+        &mut self,
+        notification: EthNotification,
+        receive_time: Instant,
+    ) -> Option<EthNotification> {
         if let Some(local_id) = self.local_id_for(&notification.subscription) {
             if let Some(sub) = self.local_to_sub.get_by_left(&local_id) {
-                sub.notify(notification.result);
+                sub.notify(TimestampedNotification { value: notification.result, receive_time });
+                return None;
             }
         }
+        Some(notification)
     }
 
     /// Get a receiver for a subscription.
@@ -0,0 +1,35 @@
+//! Mock pubsub connection.
+
+use crate::{ConnectionHandle, ConnectionInterface, PubSubConnect};
+use alloy_transport::{impl_future, TransportResult};
+use tokio::sync::mpsc;
+
+/// A [`PubSubConnect`] that connects to nothing, passing the backend side of
+/// each connection to the receiver returned by [`MockConnect::new`].
+///
+/// Tests play the part of the server through the [`ConnectionInterface`],
+/// and can force a reconnection with
+/// [`ConnectionInterface::close_with_error`].
+#[derive(Clone, Debug)]
+pub struct MockConnect(mpsc::UnboundedSender<ConnectionInterface>);
+
+impl MockConnect {
+    /// Create a new connector, along with the receiver of the backend side of
+    /// each connection.
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<ConnectionInterface>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self(tx), rx)
+    }
+}
+
+impl PubSubConnect for MockConnect {
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn connect(&self) -> impl_future!(<Output = TransportResult<ConnectionHandle>>) {
+        let (handle, interface) = ConnectionHandle::new();
+        let _ = self.0.send(interface);
+        async move { Ok(handle) }
+    }
+}
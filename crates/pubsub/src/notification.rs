@@ -0,0 +1,55 @@
+use crate::PubSubFrontend;
+use futures::{ready, Stream, StreamExt};
+use serde_json::value::RawValue;
+use std::{pin::Pin, task};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+
+/// An incoming notification that is not routed to any subscription, as a
+/// `(method, params)` pair.
+///
+/// This includes non-`eth_subscription` notifications, e.g. vendor-specific
+/// push notifications, and `eth_subscription` notifications for unknown
+/// subscriptions.
+pub type RawNotification = (String, Box<RawValue>);
+
+/// The number of unmatched notifications buffered for each receiver.
+pub(crate) const NOTIFICATION_CHANNEL_SIZE: usize = 16;
+
+/// A stream of [`RawNotification`]s received by a pubsub service.
+///
+/// The stream keeps the service alive. Notifications are skipped if the
+/// stream lags behind.
+#[derive(Debug)]
+pub struct NotificationStream {
+    _frontend: PubSubFrontend,
+    inner: BroadcastStream<RawNotification>,
+}
+
+impl NotificationStream {
+    /// Create a new stream of the unmatched notifications received by the
+    /// frontend's service.
+    pub fn new(frontend: PubSubFrontend) -> Self {
+        let inner = BroadcastStream::new(frontend.subscribe_notifications());
+        Self { _frontend: frontend, inner }
+    }
+}
+
+impl Stream for NotificationStream {
+    type Item = RawNotification;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        loop {
+            match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(notification)) => return task::Poll::Ready(Some(notification)),
+                Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
+                    debug!(%err, "notification stream lagged");
+                    continue;
+                }
+                None => return task::Poll::Ready(None),
+            }
+        }
+    }
+}
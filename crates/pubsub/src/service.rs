@@ -1,8 +1,9 @@
 use crate::{
+    frontend::ReconnectHooks,
     handle::ConnectionHandle,
     ix::PubSubInstruction,
     managers::{InFlight, RequestManager, SubscriptionManager},
-    PubSubConnect, PubSubFrontend, RawSubscription,
+    PubSubConnect, PubSubFrontend, RawNotification, RawSubscription,
 };
 use alloy_json_rpc::{Id, PubSubItem, Request, Response, ResponsePayload, SubId};
 use alloy_primitives::B256;
@@ -11,7 +12,11 @@ use alloy_transport::{
     TransportErrorKind, TransportResult,
 };
 use serde_json::value::RawValue;
-use tokio::sync::{mpsc, oneshot};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::sync::{broadcast, mpsc, oneshot};
 
 #[cfg(target_family = "wasm")]
 use wasmtimer::tokio::sleep;
@@ -19,6 +24,16 @@ use wasmtimer::tokio::sleep;
 #[cfg(not(target_family = "wasm"))]
 use tokio::time::sleep;
 
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::Instant;
+
+#[cfg(not(target_family = "wasm"))]
+use std::time::Instant;
+
+/// A warning is logged every this many reconnections, to flag persistently
+/// unstable connections.
+const RECONNECT_WARN_INTERVAL: u32 = 5;
+
 /// The service contains the backend handle, a subscription manager, and the
 /// configuration details required to reconnect.
 #[derive(Debug)]
@@ -37,28 +52,43 @@ pub(crate) struct PubSubService<T> {
 
     /// The request manager.
     pub(crate) in_flights: RequestManager,
+
+    /// The sender for notifications that are not routed to any subscription.
+    pub(crate) notifications: broadcast::Sender<RawNotification>,
+
+    /// The maximum number of concurrent subscriptions. Zero means no limit.
+    pub(crate) subscription_limit: Arc<AtomicUsize>,
+
+    /// Callbacks invoked after reconnecting.
+    pub(crate) reconnect_hooks: ReconnectHooks,
 }
 
 impl<T: PubSubConnect> PubSubService<T> {
     /// Create a new service from a connector.
     pub(crate) async fn connect(connector: T) -> TransportResult<PubSubFrontend> {
-        let handle = connector.connect().await?;
+        let mut handle = connector.connect().await?;
 
         let (tx, reqs) = mpsc::unbounded_channel();
+        let frontend = PubSubFrontend::new(tx);
+        handle.reconnect_count = frontend.reconnect_count_handle();
         let this = Self {
             handle,
             connector,
             reqs,
             subs: SubscriptionManager::default(),
             in_flights: Default::default(),
+            notifications: frontend.notifications_tx(),
+            subscription_limit: frontend.subscription_limit_handle(),
+            reconnect_hooks: frontend.reconnect_hooks_handle(),
         };
         this.spawn();
-        Ok(PubSubFrontend::new(tx))
+        Ok(frontend)
     }
 
     /// Reconnect by dropping the backend and creating a new one.
     async fn get_new_backend(&mut self) -> TransportResult<ConnectionHandle> {
         let mut handle = self.connector.try_reconnect().await?;
+        handle.reconnect_count = self.handle.reconnect_count.clone();
         std::mem::swap(&mut self.handle, &mut handle);
         Ok(handle)
     }
@@ -111,8 +141,36 @@ impl<T: PubSubConnect> PubSubService<T> {
         self.handle.to_socket.send(brv).map(drop).map_err(|_| TransportErrorKind::backend_gone())
     }
 
+    /// Returns `None` and responds with an error if the in-flight request is
+    /// a new subscription that would exceed the subscription limit.
+    ///
+    /// Subscription requests that are pending, including `pending` ones not
+    /// yet tracked by the request manager, count towards the limit.
+    fn check_subscription_limit(&self, in_flight: InFlight, pending: usize) -> Option<InFlight> {
+        let limit = self.subscription_limit.load(Ordering::Relaxed);
+        if limit == 0
+            || !in_flight.is_subscription()
+            || self.subs.contains(&in_flight.request.params_hash())
+        {
+            return Some(in_flight);
+        }
+
+        let in_flight_subs = self.in_flights.iter().filter(|(_, req)| req.is_subscription());
+        if self.subs.len() + in_flight_subs.count() + pending < limit {
+            return Some(in_flight);
+        }
+
+        debug!(limit, "rejecting subscription, limit reached");
+        let _ =
+            in_flight.tx.send(Err(TransportErrorKind::custom_str("subscription limit reached")));
+        None
+    }
+
     /// Service a request.
     fn service_request(&mut self, in_flight: InFlight) -> TransportResult<()> {
+        let Some(in_flight) = self.check_subscription_limit(in_flight, 0) else {
+            return Ok(());
+        };
         let brv = in_flight.request();
 
         self.dispatch_request(brv.serialized().to_owned())?;
@@ -121,6 +179,30 @@ impl<T: PubSubConnect> PubSubService<T> {
         Ok(())
     }
 
+    /// Service a batch of requests, dispatching them as a single JSON array.
+    fn service_batch(&mut self, in_flights: Vec<InFlight>) -> TransportResult<()> {
+        let mut pending = 0;
+        let in_flights = in_flights
+            .into_iter()
+            .filter_map(|in_flight| {
+                let in_flight = self.check_subscription_limit(in_flight, pending)?;
+                pending += in_flight.is_subscription() as usize;
+                Some(in_flight)
+            })
+            .collect::<Vec<_>>();
+        if in_flights.is_empty() {
+            return Ok(());
+        }
+
+        let reqs = in_flights.iter().map(|in_flight| in_flight.request()).collect::<Vec<_>>();
+        let brv = to_json_raw_value(&reqs)?;
+
+        self.dispatch_request(brv)?;
+        in_flights.into_iter().for_each(|in_flight| self.in_flights.insert(in_flight));
+
+        Ok(())
+    }
+
     /// Service a GetSub instruction.
     ///
     /// If the subscription exists, the waiter is sent `Some` broadcast receiver. If
@@ -147,6 +229,7 @@ impl<T: PubSubConnect> PubSubService<T> {
         trace!(?ix, "servicing instruction");
         match ix {
             PubSubInstruction::Request(in_flight) => self.service_request(in_flight),
+            PubSubInstruction::Batch(in_flights) => self.service_batch(in_flights),
             PubSubInstruction::GetSub(alias, tx) => {
                 self.service_get_sub(alias, tx);
                 Ok(())
@@ -163,12 +246,30 @@ impl<T: PubSubConnect> PubSubService<T> {
                 None => Ok(()),
             },
             PubSubItem::Notification(notification) => {
-                self.subs.notify(notification);
+                // Timestamp the notification on arrival, so that its lag doesn't
+                // include the time it spends in the subscription channel.
+                let receive_time = Instant::now();
+                if let Some(notification) = self.subs.notify(notification, receive_time) {
+                    let params = to_json_raw_value(&notification)?;
+                    self.notify_unmatched("eth_subscription".to_string(), params);
+                }
+                Ok(())
+            }
+            PubSubItem::Other { method, params } => {
+                self.notify_unmatched(method, params);
                 Ok(())
             }
+            // Ignore kinds of items this version doesn't know about.
+            _ => Ok(()),
         }
     }
 
+    /// Forward a notification that is not routed to any subscription. We
+    /// don't care if there are no receivers.
+    fn notify_unmatched(&self, method: String, params: Box<RawValue>) {
+        let _ = self.notifications.send((method, params));
+    }
+
     /// Rewrite the subscription id and insert into the subscriptions manager
     fn handle_sub_response(
         &mut self,
@@ -193,24 +294,32 @@ impl<T: PubSubConnect> PubSubService<T> {
 
     /// Attempt to reconnect with retries
     async fn reconnect_with_retries(&mut self) -> TransportResult<()> {
+        let reconnect_count = self.handle.reconnect_count.fetch_add(1, Ordering::Relaxed) + 1;
+        if reconnect_count.is_multiple_of(RECONNECT_WARN_INTERVAL) {
+            warn!(reconnect_count, "pubsub connection is unstable");
+        }
+
         let mut retry_count = 0;
         let max_retries = self.handle.max_retries;
-        let interval = self.handle.retry_interval;
         loop {
             match self.reconnect().await {
-                Ok(()) => break Ok(()),
+                Ok(()) => {
+                    self.reconnect_hooks.run();
+                    break Ok(());
+                }
                 Err(e) => {
                     retry_count += 1;
                     if retry_count >= max_retries {
                         error!("Reconnect failed after {max_retries} attempts, shutting down: {e}");
                         break Err(e);
                     }
+                    let delay = self.handle.retry_delay(retry_count - 1);
                     warn!(
                         "Reconnection attempt {retry_count}/{max_retries} failed: {e}. \
                          Retrying in {:?}s...",
-                        interval.as_secs_f64(),
+                        delay.as_secs_f64(),
                     );
-                    sleep(interval).await;
+                    sleep(delay).await;
                 }
             }
         }
@@ -256,6 +365,8 @@ impl<T: PubSubConnect> PubSubService<T> {
                 }
             };
 
+            self.connector.on_shutdown(&result);
+            self.reconnect_hooks.shutdown();
             if let Err(err) = result {
                 error!(%err, "pubsub service reconnection error");
             }
@@ -263,3 +374,65 @@ impl<T: PubSubConnect> PubSubService<T> {
         fut.spawn_task();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockConnect;
+
+    #[tokio::test]
+    async fn reconnect_hooks() {
+        let (connect, mut interfaces) = MockConnect::new();
+        let frontend = connect.into_service().await.unwrap();
+
+        let (hook_tx, mut hooks) = mpsc::unbounded_channel();
+        for n in 0..2 {
+            let hook_tx = hook_tx.clone();
+            frontend.on_reconnect(move || hook_tx.send(n).unwrap());
+        }
+
+        interfaces.recv().await.unwrap().close_with_error();
+        assert_eq!(hooks.recv().await, Some(0));
+        assert_eq!(hooks.recv().await, Some(1));
+        assert_eq!(frontend.reconnect_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn wait_for_reconnect() {
+        let (connect, mut interfaces) = MockConnect::new();
+        let frontend = connect.into_service().await.unwrap();
+
+        let reconnected = tokio::spawn(frontend.wait_for_reconnect(0));
+        interfaces.recv().await.unwrap().close_with_error();
+        assert!(reconnected.await.unwrap());
+        assert!(frontend.wait_for_reconnect(0).await);
+
+        // The service shuts down once the frontend is dropped.
+        let shut_down = frontend.wait_for_reconnect(1);
+        drop(frontend);
+        assert!(!shut_down.await);
+    }
+
+    #[tokio::test]
+    async fn response_size_limit() {
+        let (connect, mut interfaces) = MockConnect::new();
+        let frontend = connect.into_service().await.unwrap();
+        let mut interface = interfaces.recv().await.unwrap();
+
+        let mut req = Request::new("eth_getLogs", Id::Number(1), ());
+        req.meta.extensions_mut().insert(alloy_json_rpc::ResponseSizeLimit(4));
+        let resp = frontend.send(req.serialize().unwrap());
+        let result = to_json_raw_value(&"0x1234").unwrap();
+        let serve = async move {
+            interface.recv_from_frontend().await.unwrap();
+            let payload = ResponsePayload::Success(result);
+            interface
+                .send_to_frontend(PubSubItem::Response(Response { id: Id::Number(1), payload }))
+        };
+
+        let (resp, sent) = tokio::join!(resp, serve);
+        sent.unwrap();
+        let err = resp.unwrap_err();
+        assert_eq!(err.to_string(), "response too large");
+    }
+}
@@ -6,6 +6,27 @@ use std::{pin::Pin, task};
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::Instant;
+
+#[cfg(not(target_family = "wasm"))]
+use std::time::Instant;
+
+/// A subscription notification, together with the time at which it was
+/// received by the pubsub service.
+#[derive(Clone, Debug)]
+pub struct TimestampedNotification {
+    /// The notification value.
+    pub value: Box<RawValue>,
+    /// The time at which the service received the notification.
+    pub receive_time: Instant,
+}
+
+/// Returns the value of a notification received from a broadcast stream.
+fn notification_value<E>(res: Result<TimestampedNotification, E>) -> Result<Box<RawValue>, E> {
+    res.map(|notification| notification.value)
+}
+
 /// A Subscription is a feed of notifications from the server, identified by a
 /// local ID.
 ///
@@ -14,7 +35,7 @@ use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 #[derive(Debug)]
 pub struct RawSubscription {
     /// The channel via which notifications are received.
-    pub rx: broadcast::Receiver<Box<RawValue>>,
+    pub rx: broadcast::Receiver<TimestampedNotification>,
     /// The local ID of the subscription.
     pub local_id: B256,
 }
@@ -30,7 +51,7 @@ impl RawSubscription {
     ///
     /// [`blocking_recv`]: broadcast::Receiver::blocking_recv
     pub fn blocking_recv(&mut self) -> Result<Box<RawValue>, broadcast::error::RecvError> {
-        self.rx.blocking_recv()
+        notification_value(self.rx.blocking_recv())
     }
 
     /// Returns `true` if the broadcast channel is empty (i.e. there are
@@ -49,7 +70,7 @@ impl RawSubscription {
     ///
     /// [`recv`]: broadcast::Receiver::recv
     pub async fn recv(&mut self) -> Result<Box<RawValue>, broadcast::error::RecvError> {
-        self.rx.recv().await
+        notification_value(self.rx.recv().await)
     }
 
     /// Wrapper for [`resubscribe`]. Create a new Subscription, starting from
@@ -73,12 +94,15 @@ impl RawSubscription {
     ///
     /// [`try_recv`]: broadcast::Receiver::try_recv
     pub fn try_recv(&mut self) -> Result<Box<RawValue>, broadcast::error::TryRecvError> {
-        self.rx.try_recv()
+        notification_value(self.rx.try_recv())
     }
 
     /// Convert the subscription into a stream.
-    pub fn into_stream(self) -> BroadcastStream<Box<RawValue>> {
-        self.rx.into()
+    pub fn into_stream(
+        self,
+    ) -> impl Stream<Item = Result<Box<RawValue>, BroadcastStreamRecvError>> + Send + Unpin + 'static
+    {
+        BroadcastStream::from(self.rx).map(notification_value)
     }
 
     /// Convert into a typed subscription.
@@ -226,7 +250,7 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn into_stream(self) -> SubscriptionStream<T> {
         SubscriptionStream {
             id: self.inner.local_id,
-            inner: self.inner.into_stream(),
+            inner: self.inner.rx.into(),
             _pd: std::marker::PhantomData,
         }
     }
@@ -235,7 +259,7 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn into_result_stream(self) -> SubResultStream<T> {
         SubResultStream {
             id: self.inner.local_id,
-            inner: self.inner.into_stream(),
+            inner: self.inner.rx.into(),
             _pd: std::marker::PhantomData,
         }
     }
@@ -244,7 +268,7 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn into_any_stream(self) -> SubAnyStream<T> {
         SubAnyStream {
             id: self.inner.local_id,
-            inner: self.inner.into_stream(),
+            inner: self.inner.rx.into(),
             _pd: std::marker::PhantomData,
         }
     }
@@ -324,7 +348,7 @@ impl<T: DeserializeOwned> Subscription<T> {
 #[derive(Debug)]
 pub struct SubAnyStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    inner: BroadcastStream<TimestampedNotification>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -344,7 +368,9 @@ impl<T: DeserializeOwned> Stream for SubAnyStream<T> {
     ) -> task::Poll<Option<Self::Item>> {
         loop {
             match ready!(self.inner.poll_next_unpin(cx)) {
-                Some(Ok(value)) => return task::Poll::Ready(Some(value.into())),
+                Some(Ok(notification)) => {
+                    return task::Poll::Ready(Some(notification.value.into()))
+                }
                 Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
                     // This is OK.
                     debug!(%err, %self.id, "stream lagged");
@@ -362,7 +388,7 @@ impl<T: DeserializeOwned> Stream for SubAnyStream<T> {
 #[derive(Debug)]
 pub struct SubscriptionStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    inner: BroadcastStream<TimestampedNotification>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -382,14 +408,16 @@ impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
     ) -> task::Poll<Option<Self::Item>> {
         loop {
             match ready!(self.inner.poll_next_unpin(cx)) {
-                Some(Ok(value)) => match serde_json::from_str(value.get()) {
-                    Ok(item) => return task::Poll::Ready(Some(item)),
-                    Err(err) => {
-                        debug!(value = ?value.get(), %err, %self.id, "failed deserializing subscription item");
-                        error!(%err, %self.id, "failed deserializing subscription item");
-                        continue;
+                Some(Ok(TimestampedNotification { value, .. })) => {
+                    match serde_json::from_str(value.get()) {
+                        Ok(item) => return task::Poll::Ready(Some(item)),
+                        Err(err) => {
+                            debug!(value = ?value.get(), %err, %self.id, "failed deserializing subscription item");
+                            error!(%err, %self.id, "failed deserializing subscription item");
+                            continue;
+                        }
                     }
-                },
+                }
                 Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
                     // This is OK.
                     debug!(%err, %self.id, "stream lagged");
@@ -408,7 +436,7 @@ impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
 #[derive(Debug)]
 pub struct SubResultStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    inner: BroadcastStream<TimestampedNotification>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -428,8 +456,8 @@ impl<T: DeserializeOwned> Stream for SubResultStream<T> {
     ) -> task::Poll<Option<Self::Item>> {
         loop {
             match ready!(self.inner.poll_next_unpin(cx)) {
-                Some(Ok(value)) => {
-                    return task::Poll::Ready(Some(serde_json::from_str(value.get())))
+                Some(Ok(notification)) => {
+                    return task::Poll::Ready(Some(serde_json::from_str(notification.value.get())))
                 }
                 Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
                     // This is OK.
@@ -159,7 +159,7 @@ impl<'a> BatchRequest<'a> {
     /// Send the batch future via its connection.
     pub fn send(self) -> BatchFuture {
         BatchFuture::Prepared {
-            transport: self.transport.transport.clone(),
+            transport: self.transport.limited_transport(),
             requests: self.requests,
             channels: self.channels,
         }
@@ -1,5 +1,10 @@
 use crate::{BuiltInConnectionString, RpcClient};
-use alloy_transport::{BoxTransport, IntoBoxTransport, TransportConnect, TransportResult};
+use alloy_json_rpc::Request;
+use alloy_transport::{
+    layers::RequestInterceptorLayer, BoxTransport, IntoBoxTransport, TransportConnect,
+    TransportResult,
+};
+use serde_json::value::RawValue;
 use tower::{
     layer::util::{Identity, Stack},
     Layer, ServiceBuilder,
@@ -16,11 +21,13 @@ use tower::{
 #[derive(Debug)]
 pub struct ClientBuilder<L> {
     pub(crate) builder: ServiceBuilder<L>,
+    /// The request ID range of the client, if restricted.
+    pub(crate) id_range: Option<(u64, u64)>,
 }
 
 impl Default for ClientBuilder<Identity> {
     fn default() -> Self {
-        Self { builder: ServiceBuilder::new() }
+        Self { builder: ServiceBuilder::new(), id_range: None }
     }
 }
 
@@ -30,7 +37,39 @@ impl<L> ClientBuilder<L> {
     /// This is a wrapper around [`tower::ServiceBuilder::layer`]. Layers that
     /// are added first will be called with the request first.
     pub fn layer<M>(self, layer: M) -> ClientBuilder<Stack<M, L>> {
-        ClientBuilder { builder: self.builder.layer(layer) }
+        ClientBuilder { builder: self.builder.layer(layer), id_range: self.id_range }
+    }
+
+    /// Add a layer calling `f` on every outgoing request before it is sent.
+    ///
+    /// This is useful for testing and debugging, e.g. to inject trace IDs,
+    /// override block numbers, or record requests for replay. See
+    /// [`RequestInterceptorLayer`] for more details.
+    pub fn with_request_interceptor<F>(
+        self,
+        f: F,
+    ) -> ClientBuilder<Stack<RequestInterceptorLayer, L>>
+    where
+        F: Fn(&mut Request<Box<RawValue>>) + Send + Sync + 'static,
+    {
+        self.layer(RequestInterceptorLayer::new(f))
+    }
+
+    /// Restrict the request IDs of the client to the range `start..end`.
+    ///
+    /// This allows multiplexing several clients over a single connection, as
+    /// long as their ID ranges do not overlap. See
+    /// [`RpcClientInner::set_id_range`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    ///
+    /// [`RpcClientInner::set_id_range`]: crate::RpcClientInner::set_id_range
+    pub fn with_id_range(mut self, start: u64, end: u64) -> Self {
+        assert!(start < end, "empty request ID range {start}..{end}");
+        self.id_range = Some((start, end));
+        self
     }
 
     /// Create a new [`RpcClient`] with the given transport and the configured
@@ -44,7 +83,11 @@ impl<L> ClientBuilder<L> {
         T: IntoBoxTransport,
         L::Service: IntoBoxTransport,
     {
-        RpcClient::new_layered(is_local, transport, move |t| self.builder.service(t))
+        let client = RpcClient::new_layered(is_local, transport, move |t| self.builder.service(t));
+        if let Some((start, end)) = self.id_range {
+            client.set_id_range(start, end);
+        }
+        client
     }
 
     /// Convenience function to create a new [`RpcClient`] with a [`reqwest`]
@@ -104,13 +147,21 @@ impl<L> ClientBuilder<L> {
 
     /// Connect a WS transport, producing an [`RpcClient`] with the provided
     /// connection.
-    #[cfg(feature = "ws")]
+    ///
+    /// The [response size limit](RpcClient::with_response_size_limit) of the
+    /// client is set to the [message size limit] of the connection, if any.
+    ///
+    /// [message size limit]: alloy_transport_ws::WsConnect::with_message_size_limit
+    #[cfg(feature = "__ws")]
     pub async fn ws(self, ws_connect: alloy_transport_ws::WsConnect) -> TransportResult<RpcClient>
     where
         L: Layer<alloy_pubsub::PubSubFrontend>,
         L::Service: IntoBoxTransport,
     {
-        self.pubsub(ws_connect).await
+        let limit = ws_connect.message_size_limit();
+        let client = self.pubsub(ws_connect).await?;
+        client.inner().set_response_size_limit(limit);
+        Ok(client)
     }
 
     /// Connect an IPC transport, producing an [`RpcClient`] with the provided
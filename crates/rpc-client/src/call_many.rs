@@ -0,0 +1,169 @@
+use crate::RpcClient;
+use alloy_json_rpc::{ErrorPayload, RpcError, RpcSend};
+use alloy_primitives::Bytes;
+use alloy_transport::{TransportError, TransportResult};
+use serde::{Deserialize, Serialize};
+
+/// The error code used for calls that failed within an `eth_callMany`
+/// response, which only carries an error message.
+const CALL_FAILED_CODE: i64 = -32000;
+
+/// The JSON-RPC error code for methods the node does not know.
+const METHOD_NOT_FOUND_CODE: i64 = -32601;
+
+/// A bundle of transactions, as sent in the `eth_callMany` params.
+#[derive(Clone, Debug, Serialize)]
+struct Bundle<'a, Tx> {
+    transactions: &'a [Tx],
+}
+
+/// The simulation context, as sent in the `eth_callMany` params.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulationContext<'a, B> {
+    block_number: &'a B,
+}
+
+/// The result of a single call in an `eth_callMany` response.
+#[derive(Debug, Deserialize)]
+struct CallManyResult {
+    value: Option<Bytes>,
+    error: Option<String>,
+}
+
+impl From<CallManyResult> for TransportResult<Bytes> {
+    fn from(res: CallManyResult) -> Self {
+        match res {
+            CallManyResult { value: Some(value), error: None } => Ok(value),
+            CallManyResult { error, .. } => Err(RpcError::ErrorResp(ErrorPayload::new(
+                CALL_FAILED_CODE,
+                error.unwrap_or_else(|| "call failed".to_owned()),
+            ))),
+        }
+    }
+}
+
+/// Returns `true` if the error indicates that the node does not support
+/// `eth_callMany`.
+fn is_unsupported(err: &TransportError) -> bool {
+    match err {
+        RpcError::UnsupportedFeature(_) => true,
+        RpcError::ErrorResp(payload) => {
+            let message = payload.message.to_lowercase();
+            payload.code == METHOD_NOT_FOUND_CODE
+                || message.contains("method not found")
+                || message.contains("not supported")
+                || message.contains("does not exist")
+        }
+        _ => false,
+    }
+}
+
+impl RpcClient {
+    /// Execute the given calls on top of the state at `block`, returning the
+    /// output of each call.
+    ///
+    /// The calls are executed in order, as a single bundle with
+    /// `eth_callMany`. Calls may be any serializable transaction request, e.g.
+    /// `TransactionRequest` from `alloy-rpc-types-eth`, and `block` any
+    /// serializable block identifier, e.g. `BlockId`.
+    ///
+    /// `eth_callMany` is only supported by some nodes, e.g. Erigon. If the
+    /// node does not support it, i.e. responds with a method not found error,
+    /// the calls are sent as a batch of individual `eth_call` requests
+    /// instead. Each of these calls is executed on the state at `block`, so
+    /// unlike with `eth_callMany`, calls do not observe the state changes of
+    /// previous calls.
+    ///
+    /// Any other failure of the `eth_callMany` request is returned as is,
+    /// while failures of individual calls are reported in their results.
+    pub async fn eth_call_many<Tx: RpcSend, B: RpcSend>(
+        &self,
+        calls: Vec<Tx>,
+        block: B,
+    ) -> TransportResult<Vec<TransportResult<Bytes>>> {
+        let params =
+            ([Bundle { transactions: &calls }], SimulationContext { block_number: &block });
+        let res: TransportResult<Vec<Vec<CallManyResult>>> =
+            self.request("eth_callMany", params).await;
+        match res {
+            Ok(bundles) => Ok(bundles.into_iter().flatten().map(Into::into).collect()),
+            Err(err) if is_unsupported(&err) => {
+                debug!(%err, "eth_callMany unsupported, falling back to a batch of eth_call requests");
+                Ok(self.eth_call_batch(&calls, &block).await)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Send the calls as a batch of `eth_call` requests.
+    async fn eth_call_batch<Tx: RpcSend, B: RpcSend>(
+        &self,
+        calls: &[Tx],
+        block: &B,
+    ) -> Vec<TransportResult<Bytes>> {
+        let mut batch = self.new_batch();
+        let waiters: Vec<_> =
+            calls.iter().map(|call| batch.add_call("eth_call", &(call, block))).collect();
+        // Failures are reported through the waiters.
+        let _ = batch.send().await;
+
+        let mut results = Vec::with_capacity(waiters.len());
+        for waiter in waiters {
+            results.push(match waiter {
+                Ok(waiter) => waiter.await,
+                Err(err) => Err(err),
+            });
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_transport::mock::Asserter;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn call_many() {
+        let asserter = Asserter::new();
+        asserter.push_success(&json!([[{ "value": "0x01" }, { "error": "execution reverted" }]]));
+        let client = RpcClient::mocked(asserter);
+
+        let calls = vec![json!({ "to": "0x0000000000000000000000000000000000000001" }); 2];
+        let results = client.eth_call_many(calls, "latest").await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), &Bytes::from_static(&[1]));
+        let err = results[1].as_ref().unwrap_err().as_error_resp().unwrap();
+        assert_eq!(err.message, "execution reverted");
+    }
+
+    #[tokio::test]
+    async fn call_many_fallback() {
+        let asserter = Asserter::new();
+        asserter.push_failure(ErrorPayload::method_not_found());
+        asserter.push_success(&Bytes::from_static(&[1]));
+        asserter.push_success(&Bytes::from_static(&[2]));
+        let client = RpcClient::mocked(asserter);
+
+        let calls = vec![json!({}); 2];
+        let results = client.eth_call_many(calls, "latest").await.unwrap();
+        let outputs: Vec<_> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(outputs, [Bytes::from_static(&[1]), Bytes::from_static(&[2])]);
+    }
+
+    #[tokio::test]
+    async fn call_many_error() {
+        let asserter = Asserter::new();
+        asserter.push_failure(ErrorPayload::new(-32000, "header not found"));
+        asserter.push_success(&Bytes::from_static(&[1]));
+        let client = RpcClient::mocked(asserter.clone());
+
+        let calls = vec![json!({}); 2];
+        let err = client.eth_call_many(calls, "latest").await.unwrap_err();
+        assert_eq!(err.as_error_resp().unwrap().message, "header not found");
+        // No fallback requests are sent.
+        assert_eq!(asserter.read_q().len(), 1);
+    }
+}
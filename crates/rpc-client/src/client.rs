@@ -1,17 +1,35 @@
-use crate::{poller::PollerBuilder, BatchRequest, ClientBuilder, RpcCall};
-use alloy_json_rpc::{Id, Request, RpcRecv, RpcSend};
-use alloy_transport::{mock::Asserter, BoxTransport, IntoBoxTransport};
+use crate::{inflight::InflightLimit, poller::PollerBuilder, BatchRequest, ClientBuilder, RpcCall};
+use alloy_json_rpc::{Id, Request, ResponseSizeLimit, RpcError, RpcMethod, RpcRecv, RpcSend};
+use alloy_primitives::U64;
+use alloy_transport::{
+    mock::Asserter, BoxTransport, IntoBoxTransport, TransportErrorKind, TransportResult,
+};
+use dashmap::DashMap;
+use futures::{stream, Stream, StreamExt};
+use serde_json::value::RawValue;
 use std::{
     borrow::Cow,
-    ops::Deref,
+    ops::{Deref, Range},
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc, Weak,
     },
     time::Duration,
 };
 use tower::{layer::util::Identity, ServiceBuilder};
 
+#[cfg(target_family = "wasm")]
+use wasmtimer::{
+    std::Instant,
+    tokio::{sleep, timeout},
+};
+
+#[cfg(not(target_family = "wasm"))]
+use {
+    std::time::Instant,
+    tokio::time::{sleep, timeout},
+};
+
 /// An [`RpcClient`] in a [`Weak`] reference.
 pub type WeakClient = Weak<RpcClientInner>;
 
@@ -42,7 +60,7 @@ impl Clone for RpcClient {
 impl RpcClient {
     /// Create a new [`ClientBuilder`].
     pub const fn builder() -> ClientBuilder<Identity> {
-        ClientBuilder { builder: ServiceBuilder::new() }
+        ClientBuilder { builder: ServiceBuilder::new(), id_range: None }
     }
 }
 
@@ -135,6 +153,17 @@ impl RpcClient {
         self.0
     }
 
+    /// Wrap the client in an [`Arc`], e.g. for APIs that expect an
+    /// `Arc<RpcClient>`.
+    ///
+    /// [`RpcClient`] is already a reference-counted handle, so cloning it is
+    /// `O(1)` and it can be shared across tasks as-is. Cloning the returned
+    /// `Arc` is `O(1)` as well. The standard `From<T> for Arc<T>`
+    /// implementation also applies, so `Arc::from(client)` is equivalent.
+    pub fn into_arc(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
     /// Get a [`Weak`] reference to the client.
     pub fn get_weak(&self) -> WeakClient {
         Arc::downgrade(&self.0)
@@ -154,6 +183,15 @@ impl RpcClient {
         self
     }
 
+    /// Sets the maximum size in bytes of responses the client accepts, for
+    /// this client and all of its clones. A limit of `0` removes the limit.
+    ///
+    /// See [`RpcClientInner::set_response_size_limit`] for more details.
+    pub fn with_response_size_limit(self, bytes: usize) -> Self {
+        self.inner().set_response_size_limit(Some(bytes));
+        self
+    }
+
     /// Build a poller that polls a method with the given parameters.
     ///
     /// See [`PollerBuilder`] for examples and more details.
@@ -169,6 +207,38 @@ impl RpcClient {
         PollerBuilder::new(self.get_weak(), method, params)
     }
 
+    /// Send a request for each input, with at most `concurrency` requests in
+    /// flight at a time.
+    ///
+    /// The params of each request are created from its input by
+    /// `make_params`. Responses are yielded in completion order, not in input
+    /// order. Requests are only sent while the stream is polled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `concurrency` is `0`.
+    pub fn concurrent_map<I, Params, Resp, F>(
+        &self,
+        inputs: I,
+        concurrency: usize,
+        method: impl Into<Cow<'static, str>>,
+        make_params: F,
+    ) -> impl Stream<Item = TransportResult<Resp>> + Send + 'static
+    where
+        I: IntoIterator,
+        I::IntoIter: Send + 'static,
+        Params: RpcSend + 'static,
+        Resp: RpcRecv,
+        F: Fn(I::Item) -> Params + Send + 'static,
+    {
+        assert!(concurrency > 0, "concurrency must be greater than 0");
+        let client = self.clone();
+        let method = method.into();
+        stream::iter(inputs)
+            .map(move |input| client.request(method.clone(), make_params(input)))
+            .buffer_unordered(concurrency)
+    }
+
     /// Boxes the transport.
     #[deprecated(since = "0.9.0", note = "`RpcClient` is now always boxed")]
     #[expect(clippy::missing_const_for_fn)]
@@ -220,8 +290,22 @@ pub struct RpcClientInner {
     pub(crate) is_local: bool,
     /// The next request ID to use.
     pub(crate) id: AtomicU64,
+    /// The first request ID of the ID range.
+    pub(crate) id_start: AtomicU64,
+    /// The end of the ID range, exclusive.
+    pub(crate) id_end: AtomicU64,
     /// The poll interval for the client in milliseconds.
     pub(crate) poll_interval: AtomicU64,
+    /// Cached responses of [`RpcClientInner::request_with_cache_key`].
+    pub(crate) cache: DashMap<u64, (Instant, Box<RawValue>)>,
+    /// The time-to-live of cached responses in milliseconds.
+    pub(crate) cache_ttl: AtomicU64,
+    /// The maximum response size in bytes, `0` if unlimited.
+    pub(crate) response_size_limit: AtomicUsize,
+    /// The cached chain ID, `0` if not fetched yet.
+    pub(crate) chain_id: AtomicU64,
+    /// The maximum number of requests in flight.
+    pub(crate) inflight: InflightLimit,
 }
 
 impl RpcClientInner {
@@ -237,7 +321,14 @@ impl RpcClientInner {
             pubsub: None,
             is_local,
             id: AtomicU64::new(0),
+            id_start: AtomicU64::new(0),
+            id_end: AtomicU64::new(u64::MAX),
             poll_interval: if is_local { AtomicU64::new(250) } else { AtomicU64::new(7000) },
+            cache: DashMap::new(),
+            cache_ttl: AtomicU64::new(1000),
+            response_size_limit: AtomicUsize::new(0),
+            chain_id: AtomicU64::new(0),
+            inflight: InflightLimit::default(),
         }
     }
 
@@ -261,6 +352,31 @@ impl RpcClientInner {
         Self { id: AtomicU64::new(id), ..self }
     }
 
+    /// Returns the range request IDs are allocated from.
+    pub fn id_range(&self) -> Range<u64> {
+        self.id_start.load(Ordering::Relaxed)..self.id_end.load(Ordering::Relaxed)
+    }
+
+    /// Restrict request IDs to the range `start..end`, and reset the ID
+    /// counter to `start`. Once `end` is reached, IDs wrap around to `start`.
+    ///
+    /// This allows multiplexing several clients over a single connection,
+    /// e.g. by sharing one `PubSubFrontend`, as long as their ID ranges do not
+    /// overlap. Responses are routed by ID, so each client receives the
+    /// responses to its own requests.
+    ///
+    /// Default is `0..u64::MAX`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn set_id_range(&self, start: u64, end: u64) {
+        assert!(start < end, "empty request ID range {start}..{end}");
+        self.id_start.store(start, Ordering::Relaxed);
+        self.id_end.store(end, Ordering::Relaxed);
+        self.id.store(start, Ordering::Relaxed);
+    }
+
     /// Returns the default poll interval (milliseconds) for the client.
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.poll_interval.load(Ordering::Relaxed))
@@ -272,6 +388,71 @@ impl RpcClientInner {
         self.poll_interval.store(poll_interval.as_millis() as u64, Ordering::Relaxed);
     }
 
+    /// Returns the time-to-live of responses cached by
+    /// [`request_with_cache_key`](Self::request_with_cache_key).
+    pub fn cache_ttl(&self) -> Duration {
+        Duration::from_millis(self.cache_ttl.load(Ordering::Relaxed))
+    }
+
+    /// Set the time-to-live of responses cached by
+    /// [`request_with_cache_key`](Self::request_with_cache_key). Default: 1s.
+    pub fn set_cache_ttl(&self, cache_ttl: Duration) {
+        self.cache_ttl.store(cache_ttl.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Removes all responses cached by
+    /// [`request_with_cache_key`](Self::request_with_cache_key).
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Returns the maximum size in bytes of responses the client accepts, if
+    /// any.
+    pub fn response_size_limit(&self) -> Option<usize> {
+        match self.response_size_limit.load(Ordering::Relaxed) {
+            0 => None,
+            limit => Some(limit),
+        }
+    }
+
+    /// Set the maximum size in bytes of responses the client accepts. Default:
+    /// unlimited. `None` and `Some(0)` both remove the limit.
+    ///
+    /// The limit is attached to every request as a [`ResponseSizeLimit`]
+    /// extension. HTTP transports stop reading the response body once it
+    /// exceeds the limit, and return a `"response too large"` error. Pubsub
+    /// transports return the same error for larger results, but receive
+    /// messages in full, so the connection should limit their size too, e.g.
+    /// via `WsConnect::with_message_size_limit`. Clients connected with
+    /// `ClientBuilder::ws` start with that limit.
+    pub fn set_response_size_limit(&self, limit: Option<usize>) {
+        self.response_size_limit.store(limit.unwrap_or_default(), Ordering::Relaxed);
+    }
+
+    /// Returns the maximum number of requests in flight, `0` if unlimited.
+    pub fn max_inflight_requests(&self) -> usize {
+        self.inflight.get()
+    }
+
+    /// Set the maximum number of requests in flight, `0` for unlimited.
+    /// Default: unlimited.
+    ///
+    /// Requests and batches prepared by the client wait for a free slot when
+    /// they are sent, and hold it until their response is received. The limit
+    /// may be changed at any time. Raising it lets waiting requests through
+    /// immediately, while lowering it lets requests already in flight
+    /// complete, and admits new requests once the number in flight is below
+    /// the new limit. Requests prepared while unlimited are not counted.
+    pub fn set_max_inflight_requests(&self, limit: usize) {
+        self.inflight.set(limit);
+    }
+
+    /// Returns the transport to send prepared requests through, which
+    /// enforces the [in-flight limit](Self::set_max_inflight_requests).
+    pub(crate) fn limited_transport(&self) -> BoxTransport {
+        self.inflight.wrap(&self.transport)
+    }
+
     /// Returns a reference to the underlying transport.
     #[inline]
     pub const fn transport(&self) -> &BoxTransport {
@@ -290,7 +471,23 @@ impl RpcClientInner {
         self.transport
     }
 
-    /// Returns a reference to the pubsub frontend if the transport supports it.
+    /// Returns `true` if the transport supports subscriptions, i.e. if it is
+    /// backed by a pubsub service.
+    #[inline]
+    #[cfg_attr(not(feature = "pubsub"), expect(clippy::missing_const_for_fn))]
+    pub fn supports_subscriptions(&self) -> bool {
+        #[cfg(feature = "pubsub")]
+        {
+            self.pubsub_frontend().is_some()
+        }
+        #[cfg(not(feature = "pubsub"))]
+        {
+            false
+        }
+    }
+
+    /// Returns a reference to the pubsub frontend if the transport supports it,
+    /// or `None` otherwise, e.g. for HTTP transports.
     #[cfg(feature = "pubsub")]
     #[inline]
     #[track_caller]
@@ -313,6 +510,23 @@ impl RpcClientInner {
         self.pubsub_frontend().expect("called pubsub_frontend on a non-pubsub transport")
     }
 
+    /// Register a callback that is invoked every time the pubsub transport
+    /// re-established its connection, e.g. to re-create server-side filters.
+    ///
+    /// Multiple callbacks may be registered, and are invoked in registration
+    /// order. See [`PubSubFrontend::on_reconnect`] for more details.
+    ///
+    /// Returns [`TransportErrorKind::PubsubUnavailable`] if the transport does
+    /// not support pubsub.
+    ///
+    /// [`PubSubFrontend::on_reconnect`]: alloy_pubsub::PubSubFrontend::on_reconnect
+    #[cfg(feature = "pubsub")]
+    pub fn on_reconnect(&self, hook: impl Fn() + Send + Sync + 'static) -> TransportResult<()> {
+        let pubsub = self.pubsub_frontend().ok_or(TransportErrorKind::PubsubUnavailable)?;
+        pubsub.on_reconnect(hook);
+        Ok(())
+    }
+
     /// Build a `JsonRpcRequest` with the given method and params.
     ///
     /// This function reserves an ID for the request, however the request is not sent.
@@ -324,7 +538,11 @@ impl RpcClientInner {
         method: impl Into<Cow<'static, str>>,
         params: Params,
     ) -> Request<Params> {
-        Request::new(method, self.next_id(), params)
+        let mut request = Request::new(method, self.next_id(), params);
+        if let Some(limit) = self.response_size_limit() {
+            request.meta.extensions_mut().insert(ResponseSizeLimit(limit));
+        }
+        request
     }
 
     /// `true` if the client believes the transport is local.
@@ -345,9 +563,20 @@ impl RpcClientInner {
     }
 
     /// Reserve a request ID value. This is used to generate request IDs.
+    ///
+    /// A counter outside of the ID range, e.g. set with
+    /// [`with_id`](Self::with_id), restarts at the start of the range.
     #[inline]
     fn increment_id(&self) -> u64 {
-        self.id.fetch_add(1, Ordering::Relaxed)
+        let Range { start, end } = self.id_range();
+        let clamp = |id: u64| if (start..end).contains(&id) { id } else { start };
+        let next = |id: u64| {
+            let id = clamp(id);
+            Some(if id + 1 >= end { start } else { id + 1 })
+        };
+        // `next` always returns `Some`, so this never fails.
+        let prev = self.id.fetch_update(Ordering::Relaxed, Ordering::Relaxed, next);
+        clamp(prev.unwrap_or_else(|id| id))
     }
 
     /// Reserve a request ID u64.
@@ -373,7 +602,7 @@ impl RpcClientInner {
         params: Params,
     ) -> RpcCall<Params, Resp> {
         let request = self.make_request(method, params);
-        RpcCall::new(request, self.transport.clone())
+        RpcCall::new(request, self.limited_transport())
     }
 
     /// Prepares an [`RpcCall`] with no parameters.
@@ -386,6 +615,135 @@ impl RpcClientInner {
         self.request(method, [])
     }
 
+    /// Prepares an [`RpcCall`] for a typed [`RpcMethod`].
+    ///
+    /// The method name, parameters and response type are all taken from the
+    /// [`RpcMethod`] implementation.
+    pub fn call_typed<C: RpcMethod>(&self, call: C) -> RpcCall<C::Params, C::Return> {
+        self.request(C::METHOD, call.into_params())
+    }
+
+    /// Repeatedly sends a request every `interval` until `condition` holds for
+    /// the response, returning that response.
+    ///
+    /// This is a building block for waiting on chain state, e.g. for a
+    /// transaction to be mined. For streams of responses, see
+    /// [`RpcClient::prepare_static_poller`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any request fails, or if `condition` does not hold
+    /// within `timeout`.
+    pub async fn poll_request<Params: RpcSend, Resp: RpcRecv>(
+        &self,
+        method: impl Into<Cow<'static, str>>,
+        params: Params,
+        interval: Duration,
+        timeout: Duration,
+        condition: impl Fn(&Resp) -> bool,
+    ) -> TransportResult<Resp> {
+        let method = method.into();
+        let started = Instant::now();
+        loop {
+            let resp: Resp = self.request(method.clone(), params.clone()).await?;
+            if condition(&resp) {
+                return Ok(resp);
+            }
+
+            let elapsed = started.elapsed();
+            if elapsed >= timeout {
+                return Err(TransportErrorKind::custom_str(&format!(
+                    "{method} did not satisfy the condition within {timeout:?}"
+                )));
+            }
+            sleep(interval.min(timeout - elapsed)).await;
+        }
+    }
+
+    /// Sends a request, cancelling it if it does not complete before
+    /// `deadline`.
+    ///
+    /// This allows a single deadline to be shared across the calls of a
+    /// multi-step flow, e.g. an approval followed by a swap.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransportErrorKind::Timeout`] if the deadline passes before
+    /// the response is received. No request is sent if the deadline has
+    /// already passed.
+    pub async fn send_with_deadline<Params: RpcSend, Resp: RpcRecv>(
+        &self,
+        deadline: Instant,
+        method: impl Into<Cow<'static, str>>,
+        params: Params,
+    ) -> TransportResult<Resp> {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(TransportErrorKind::timeout());
+        }
+        timeout(remaining, self.request(method, params))
+            .await
+            .unwrap_or_else(|_| Err(TransportErrorKind::timeout()))
+    }
+
+    /// Sends a request, sharing its result with other requests using the same
+    /// `cache_key`.
+    ///
+    /// If a response for `cache_key` was stored less than [`cache_ttl`] ago, it
+    /// is returned without sending a request. Otherwise the request is sent and
+    /// its response is cached.
+    ///
+    /// The caller is responsible for choosing keys that identify logically
+    /// identical requests, i.e. the same method and params. Requests using the
+    /// same key with different methods or params will share results.
+    ///
+    /// [`cache_ttl`]: Self::cache_ttl
+    pub async fn request_with_cache_key<Params: RpcSend, Resp: RpcRecv>(
+        &self,
+        cache_key: u64,
+        method: impl Into<Cow<'static, str>>,
+        params: Params,
+    ) -> TransportResult<Resp> {
+        let ttl = self.cache_ttl();
+        if let Some(entry) = self.cache.get(&cache_key) {
+            let (cached_at, value) = &*entry;
+            if cached_at.elapsed() < ttl {
+                return serde_json::from_str(value.get())
+                    .map_err(|err| RpcError::deser_err(err, value.get()));
+            }
+        }
+
+        let value: Box<RawValue> = self.request(method, params).await?;
+        let resp = serde_json::from_str(value.get())
+            .map_err(|err| RpcError::deser_err(err, value.get()))?;
+        // Prune expired responses, so that the cache does not grow without
+        // bound with many distinct cache keys.
+        self.cache.retain(|_, (cached_at, _)| cached_at.elapsed() < ttl);
+        self.cache.insert(cache_key, (Instant::now(), value));
+        Ok(resp)
+    }
+
+    /// Returns the chain ID of the connected chain.
+    ///
+    /// `eth_chainId` is only requested on the first call, subsequent calls
+    /// return the cached value. Use
+    /// [`chain_id_uncached`](Self::chain_id_uncached) to re-fetch it.
+    pub async fn chain_id(&self) -> TransportResult<u64> {
+        match self.chain_id.load(Ordering::Relaxed) {
+            0 => self.chain_id_uncached().await,
+            chain_id => Ok(chain_id),
+        }
+    }
+
+    /// Requests the chain ID of the connected chain, bypassing and updating
+    /// the value cached by [`chain_id`](Self::chain_id).
+    pub async fn chain_id_uncached(&self) -> TransportResult<u64> {
+        let chain_id: U64 = self.request_noparams("eth_chainId").await?;
+        let chain_id = chain_id.to::<u64>();
+        self.chain_id.store(chain_id, Ordering::Relaxed);
+        Ok(chain_id)
+    }
+
     /// Type erase the service in the transport, allowing it to be used in a
     /// generic context.
     #[deprecated(since = "0.9.0", note = "`RpcClientInner` is now always boxed")]
@@ -398,7 +756,7 @@ impl RpcClientInner {
 #[cfg(feature = "pubsub")]
 mod pubsub_impl {
     use super::*;
-    use alloy_pubsub::{PubSubConnect, RawSubscription, Subscription};
+    use alloy_pubsub::{NotificationStream, PubSubConnect, RawSubscription, Subscription};
     use alloy_transport::TransportResult;
 
     impl RpcClientInner {
@@ -454,6 +812,23 @@ mod pubsub_impl {
         pub fn set_channel_size(&self, size: usize) {
             self.expect_pubsub_frontend().set_channel_size(size)
         }
+
+        /// Consume the client, returning a stream of the incoming notifications
+        /// that are not routed to any subscription, e.g. vendor-specific push
+        /// notifications, as `(method, params)` pairs.
+        ///
+        /// The stream keeps the pubsub service alive. For multiple consumers, use
+        /// [`PubSubFrontend::subscribe_notifications`] instead.
+        ///
+        /// [`PubSubFrontend::subscribe_notifications`]: alloy_pubsub::PubSubFrontend::subscribe_notifications
+        ///
+        /// # Panics
+        ///
+        /// Panics if the transport does not support pubsub.
+        #[track_caller]
+        pub fn into_notification_stream(self) -> NotificationStream {
+            self.expect_pubsub_frontend().notification_stream()
+        }
     }
 }
 
@@ -462,6 +837,191 @@ mod tests {
     use super::*;
     use similar_asserts::assert_eq;
 
+    #[tokio::test]
+    async fn test_call_typed() {
+        struct EthChainId;
+
+        impl RpcMethod for EthChainId {
+            const METHOD: &'static str = "eth_chainId";
+            type Params = ();
+            type Return = alloy_primitives::U64;
+
+            fn into_params(self) -> Self::Params {}
+        }
+
+        let asserter = Asserter::new();
+        asserter.push_success(&"0x1");
+        let client = RpcClient::mocked(asserter);
+
+        let chain_id = client.call_typed(EthChainId).await.unwrap();
+        assert_eq!(chain_id.to::<u64>(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chain_id_cached() {
+        let asserter = Asserter::new();
+        asserter.push_success(&"0x1");
+        asserter.push_success(&"0xa");
+        let client = RpcClient::mocked(asserter);
+
+        assert_eq!(client.chain_id().await.unwrap(), 1);
+        assert_eq!(client.chain_id().await.unwrap(), 1);
+        assert_eq!(client.chain_id_uncached().await.unwrap(), 10);
+        assert_eq!(client.chain_id().await.unwrap(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_send_with_deadline() {
+        let asserter = Asserter::new();
+        asserter.push_success(&"0x1");
+        let client = RpcClient::mocked(asserter);
+
+        let deadline = Instant::now() + Duration::from_secs(1);
+        let n: alloy_primitives::U64 =
+            client.send_with_deadline(deadline, "eth_blockNumber", ()).await.unwrap();
+        assert_eq!(n.to::<u64>(), 1);
+
+        let deadline = Instant::now() - Duration::from_secs(1);
+        let err = client
+            .send_with_deadline::<_, alloy_primitives::U64>(deadline, "eth_blockNumber", ())
+            .await
+            .unwrap_err();
+        assert!(err.as_transport_err().is_some_and(TransportErrorKind::is_timeout));
+    }
+
+    #[test]
+    fn test_id_range() {
+        let client = ClientBuilder::default()
+            .with_id_range(10, 13)
+            .transport(alloy_transport::mock::MockTransport::new(Asserter::new()), true);
+        assert_eq!(client.id_range(), 10..13);
+
+        let ids: Vec<_> = (0..4).map(|_| client.next_id()).collect();
+        assert_eq!(ids, [10, 11, 12, 10].map(Id::Number));
+    }
+
+    #[test]
+    fn test_id_outside_range() {
+        // IDs below or above the range restart at its start.
+        for id in [5, 13, 20] {
+            let transport = alloy_transport::mock::MockTransport::new(Asserter::new());
+            let inner = RpcClientInner::new(transport, true);
+            inner.set_id_range(10, 13);
+            let inner = inner.with_id(id);
+            let ids: Vec<_> = (0..3).map(|_| inner.next_id()).collect();
+            assert_eq!(ids, [10, 11, 12].map(Id::Number), "starting at {id}");
+        }
+    }
+
+    #[test]
+    fn test_response_size_limit() {
+        let client = RpcClient::mocked(Asserter::new());
+        assert_eq!(client.response_size_limit(), None);
+        let req = client.make_request("eth_chainId", ());
+        assert!(req.meta.extensions().get::<ResponseSizeLimit>().is_none());
+
+        let client = client.with_response_size_limit(1024);
+        assert_eq!(client.response_size_limit(), Some(1024));
+        let req = client.make_request("eth_chainId", ());
+        assert_eq!(
+            req.meta.extensions().get::<ResponseSizeLimit>(),
+            Some(&ResponseSizeLimit(1024))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_map() {
+        let asserter = Asserter::new();
+        for n in 1..=3u64 {
+            asserter.push_success(&U64::from(n * 10));
+        }
+        let client = RpcClient::mocked(asserter);
+
+        let mut results: Vec<U64> = client
+            .concurrent_map(1..=3u64, 2, "eth_getBlockByNumber", |n| (U64::from(n), false))
+            .map(|res: TransportResult<U64>| res.unwrap())
+            .collect()
+            .await;
+        results.sort();
+        assert_eq!(results, [10, 20, 30].map(U64::from));
+    }
+
+    #[tokio::test]
+    async fn test_max_inflight_requests() {
+        let asserter = Asserter::new();
+        let client = RpcClient::mocked(asserter.clone());
+        assert_eq!(client.max_inflight_requests(), 0);
+
+        client.set_max_inflight_requests(1);
+        assert_eq!(client.max_inflight_requests(), 1);
+        asserter.push_success(&U64::from(1));
+        let first = client.request_noparams::<U64>("eth_blockNumber");
+        asserter.push_success(&U64::from(2));
+        let second = client.request_noparams::<U64>("eth_blockNumber");
+        let (first, second) = tokio::join!(first, second);
+        assert_eq!(first.unwrap() + second.unwrap(), U64::from(3));
+
+        client.set_max_inflight_requests(0);
+        assert_eq!(client.max_inflight_requests(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_poll_request() {
+        let asserter = Asserter::new();
+        for n in 1u64..=3 {
+            asserter.push_success(&n);
+        }
+        let client = RpcClient::mocked(asserter.clone());
+
+        let interval = Duration::from_millis(1);
+        let n: u64 = client
+            .poll_request("eth_blockNumber", (), interval, Duration::from_secs(1), |n| *n >= 3)
+            .await
+            .unwrap();
+        assert_eq!(n, 3);
+
+        asserter.push_success(&1u64);
+        let res = client
+            .poll_request("eth_blockNumber", (), interval, Duration::ZERO, |n: &u64| *n >= 3)
+            .await;
+        assert!(res.unwrap_err().to_string().contains("did not satisfy the condition"));
+    }
+
+    #[tokio::test]
+    async fn test_request_with_cache_key() {
+        let asserter = Asserter::new();
+        asserter.push_success(&1u64);
+        asserter.push_success(&2u64);
+        let client = RpcClient::mocked(asserter);
+
+        let first: u64 = client.request_with_cache_key(1, "eth_chainId", ()).await.unwrap();
+        let cached: u64 = client.request_with_cache_key(1, "eth_chainId", ()).await.unwrap();
+        assert_eq!(first, 1);
+        assert_eq!(cached, 1);
+
+        client.set_cache_ttl(Duration::ZERO);
+        let fresh: u64 = client.request_with_cache_key(1, "eth_chainId", ()).await.unwrap();
+        assert_eq!(fresh, 2);
+    }
+
+    #[tokio::test]
+    async fn test_request_with_cache_key_prunes_expired() {
+        let asserter = Asserter::new();
+        let client = RpcClient::mocked(asserter.clone());
+        client.set_cache_ttl(Duration::from_millis(10));
+
+        for key in 0..3 {
+            asserter.push_success(&key);
+            let _: u64 = client.request_with_cache_key(key, "eth_chainId", ()).await.unwrap();
+        }
+        assert_eq!(client.cache.len(), 3);
+
+        sleep(Duration::from_millis(20)).await;
+        asserter.push_success(&3u64);
+        let _: u64 = client.request_with_cache_key(3, "eth_chainId", ()).await.unwrap();
+        assert_eq!(client.cache.len(), 1);
+    }
+
     #[test]
     fn test_client_with_poll_interval() {
         let poll_interval = Duration::from_millis(5_000);
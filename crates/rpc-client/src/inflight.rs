@@ -0,0 +1,171 @@
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_transport::{BoxTransport, TransportError, TransportFut};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    task::{Context, Poll},
+};
+use tokio::sync::Semaphore;
+use tower::Service;
+
+/// A runtime-tunable limit on the number of requests in flight.
+#[derive(Debug, Default)]
+pub(crate) struct InflightLimit {
+    /// The current limit, `0` if unlimited.
+    limit: AtomicUsize,
+    /// The permits of the current limit, `None` if unlimited.
+    permits: Mutex<Option<Arc<Permits>>>,
+}
+
+impl InflightLimit {
+    /// Returns the current limit, `0` if unlimited.
+    pub(crate) fn get(&self) -> usize {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    /// Set the limit, `0` for unlimited.
+    ///
+    /// Raising the limit releases new permits immediately. Lowering it
+    /// removes available permits, and withholds permits of requests in flight
+    /// as they complete until the new limit is reached.
+    pub(crate) fn set(&self, limit: usize) {
+        let mut permits = self.permits.lock().unwrap_or_else(PoisonError::into_inner);
+        let old = self.limit.swap(limit, Ordering::Relaxed);
+        match (old, limit, permits.as_ref()) {
+            (old, new, Some(permits)) if old != 0 && new != 0 => {
+                if new > old {
+                    permits.add(new - old);
+                } else {
+                    permits.remove(old - new);
+                }
+            }
+            // Requests sent while unlimited are not counted, so the limit
+            // starts out with fresh permits.
+            (_, 0, _) => *permits = None,
+            (_, new, _) => *permits = Some(Arc::new(Permits::new(new))),
+        }
+    }
+
+    /// Wrap the transport to acquire a permit for each request, if limited.
+    pub(crate) fn wrap(&self, transport: &BoxTransport) -> BoxTransport {
+        let permits = self.permits.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        permits.map_or_else(
+            || transport.clone(),
+            |permits| BoxTransport::new(InflightLimited { inner: transport.clone(), permits }),
+        )
+    }
+}
+
+/// The permits of an [`InflightLimit`].
+#[derive(Debug)]
+struct Permits {
+    semaphore: Semaphore,
+    /// The number of permits to withhold when requests in flight complete,
+    /// after the limit was lowered.
+    debt: AtomicUsize,
+}
+
+impl Permits {
+    fn new(limit: usize) -> Self {
+        Self { semaphore: Semaphore::new(limit), debt: AtomicUsize::new(0) }
+    }
+
+    /// Add `n` permits, paying off the debt first.
+    fn add(&self, n: usize) {
+        let paid = self
+            .debt
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |debt| Some(debt.saturating_sub(n)))
+            .unwrap_or_else(|debt| debt)
+            .min(n);
+        self.semaphore.add_permits(n - paid);
+    }
+
+    /// Remove `n` permits, taking on debt for those in use.
+    fn remove(&self, n: usize) {
+        let forgotten = self.semaphore.forget_permits(n);
+        self.debt.fetch_add(n - forgotten, Ordering::Relaxed);
+    }
+
+    /// Wait for a permit, which is released when the returned guard is
+    /// dropped.
+    async fn acquire(self: Arc<Self>) -> PermitGuard {
+        // The semaphore is never closed.
+        self.semaphore.acquire().await.expect("semaphore closed").forget();
+        PermitGuard(self)
+    }
+}
+
+/// Releases a permit of [`Permits`] when dropped, unless it is withheld to
+/// pay off debt.
+struct PermitGuard(Arc<Permits>);
+
+impl Drop for PermitGuard {
+    fn drop(&mut self) {
+        let withheld = self
+            .0
+            .debt
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |debt| debt.checked_sub(1))
+            .is_ok();
+        if !withheld {
+            self.0.semaphore.add_permits(1);
+        }
+    }
+}
+
+/// A transport that holds a permit of an [`InflightLimit`] for the duration
+/// of each request.
+#[derive(Clone, Debug)]
+struct InflightLimited {
+    inner: BoxTransport,
+    permits: Arc<Permits>,
+}
+
+impl Service<RequestPacket> for InflightLimited {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let permits = self.permits.clone();
+        Box::pin(async move {
+            let _permit = permits.acquire().await;
+            inner.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tune_limit() {
+        let limit = InflightLimit::default();
+        limit.set(2);
+        let permits = limit.permits.lock().unwrap().clone().unwrap();
+        let a = permits.clone().acquire().await;
+        let b = permits.clone().acquire().await;
+        assert_eq!(permits.semaphore.available_permits(), 0);
+
+        // Lowering the limit withholds a permit in use.
+        limit.set(1);
+        drop(a);
+        assert_eq!(permits.semaphore.available_permits(), 0);
+        drop(b);
+        assert_eq!(permits.semaphore.available_permits(), 1);
+
+        limit.set(3);
+        assert_eq!(permits.semaphore.available_permits(), 3);
+        assert_eq!(limit.get(), 3);
+
+        limit.set(0);
+        assert!(limit.permits.lock().unwrap().is_none());
+    }
+}
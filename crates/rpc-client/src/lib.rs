@@ -16,21 +16,43 @@ mod builder;
 pub use builder::ClientBuilder;
 
 mod builtin;
-pub use builtin::BuiltInConnectionString;
+pub use builtin::{BuiltInConnectionString, TransportKind, ValidationError};
 
 mod call;
 pub use call::RpcCall;
 
+mod call_many;
+
 mod client;
 pub use client::{ClientRef, NoParams, RpcClient, RpcClientInner, WeakClient};
 
+mod inflight;
+
+pub mod mock;
+
+mod nonce;
+pub use nonce::NonceManagingClient;
+
 mod poller;
 pub use poller::{PollChannel, PollerBuilder, PollerStream};
 
-#[cfg(feature = "ws")]
+mod pool;
+pub use pool::{PoolStrategy, RpcClientPool};
+
+mod traced;
+pub use traced::TracedCall;
+
+mod watch;
+
+#[cfg(feature = "pubsub")]
+mod subscription;
+#[cfg(feature = "pubsub")]
+pub use subscription::{FilteredSubscription, ReconnectingSubscription, SubscriptionBuilder};
+
+#[cfg(feature = "__ws")]
 pub use alloy_transport_ws::WsConnect;
 
-#[cfg(all(feature = "ws", not(target_family = "wasm")))]
+#[cfg(all(feature = "__ws", not(target_family = "wasm")))]
 pub use alloy_transport_ws::WebSocketConfig;
 
 #[cfg(all(feature = "ipc", not(target_family = "wasm")))]
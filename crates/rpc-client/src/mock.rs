@@ -0,0 +1,223 @@
+//! Transports for recording and replaying RPC calls in tests.
+//!
+//! [`RecordingTransport`] wraps another transport and records every call made
+//! through it. The recorded calls can be played back with a
+//! [`ReplayTransport`], which returns the recorded responses in order without
+//! contacting a node.
+//!
+//! ```no_run
+//! # async fn example(
+//! #     transport: impl alloy_transport::Transport + Clone,
+//! # ) -> alloy_transport::TransportResult<()> {
+//! use alloy_primitives::U64;
+//! use alloy_rpc_client::{
+//!     mock::{RecordingTransport, ReplayTransport},
+//!     RpcClient,
+//! };
+//!
+//! let recorder = RecordingTransport::new(transport);
+//! let client = RpcClient::new(recorder.clone(), false);
+//! let live: U64 = client.request_noparams("eth_blockNumber").await?;
+//!
+//! let client = RpcClient::new(ReplayTransport::from_recording(recorder.into_recording()), true);
+//! let replayed: U64 = client.request_noparams("eth_blockNumber").await?;
+//! assert_eq!(live, replayed);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, ResponsePayload, SerializedRequest};
+use alloy_transport::{Transport, TransportError, TransportErrorKind, TransportFut};
+use serde_json::value::RawValue;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll},
+};
+use tower::Service;
+
+/// A single RPC call, as recorded by a [`RecordingTransport`].
+#[derive(Clone, Debug)]
+pub struct RecordedCall {
+    /// The method name.
+    pub method: String,
+    /// The serialized params, if any.
+    pub params: Option<Box<RawValue>>,
+    /// The response payload returned by the server.
+    pub response: ResponsePayload,
+}
+
+/// A transport that records every call made through the inner transport.
+///
+/// Clones share the same recording, so a clone can be kept to retrieve the
+/// recording after the transport was moved into a client. Calls of a batch
+/// are recorded in request order. Requests that fail at the transport level
+/// are not recorded.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Clone, Debug)]
+pub struct RecordingTransport<T> {
+    inner: T,
+    calls: Arc<Mutex<Vec<RecordedCall>>>,
+}
+
+impl<T> RecordingTransport<T> {
+    /// Create a new recording transport wrapping the given transport.
+    pub fn new(inner: T) -> Self {
+        Self { inner, calls: Default::default() }
+    }
+
+    /// Returns a reference to the inner transport.
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a copy of the calls recorded so far.
+    pub fn recording(&self) -> Vec<RecordedCall> {
+        self.calls.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Take the calls recorded so far, leaving the shared recording empty.
+    pub fn into_recording(self) -> Vec<RecordedCall> {
+        std::mem::take(&mut *self.calls.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Record the responses to the requests of a packet.
+    fn record(&self, req: &RequestPacket, resp: &ResponsePacket) {
+        let mut calls = self.calls.lock().unwrap_or_else(PoisonError::into_inner);
+        for req in req.requests() {
+            if let Some(resp) = resp.find_by_id(req.id()) {
+                calls.push(RecordedCall {
+                    method: req.method().to_owned(),
+                    params: req.params().map(ToOwned::to_owned),
+                    response: resp.payload.clone(),
+                });
+            }
+        }
+    }
+}
+
+impl<T> Service<RequestPacket> for RecordingTransport<T>
+where
+    T: Transport + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let this = self.clone();
+        let fut = self.inner.call(req.clone());
+        Box::pin(async move {
+            let resp = fut.await?;
+            this.record(&req, &resp);
+            Ok(resp)
+        })
+    }
+}
+
+/// A transport that plays back calls recorded by a [`RecordingTransport`].
+///
+/// Recorded responses are returned in order, regardless of the params of the
+/// request. Clones share the same queue of recorded calls.
+///
+/// # Panics
+///
+/// Calls panic if the method name does not match the next recorded call.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Clone, Debug)]
+pub struct ReplayTransport {
+    calls: Arc<Mutex<VecDeque<RecordedCall>>>,
+}
+
+impl ReplayTransport {
+    /// Create a new replay transport from recorded calls.
+    pub fn from_recording(calls: Vec<RecordedCall>) -> Self {
+        Self { calls: Arc::new(Mutex::new(calls.into())) }
+    }
+
+    /// Returns the number of recorded calls that have not been played back.
+    pub fn remaining(&self) -> usize {
+        self.calls.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Play back the next recorded call for the given request.
+    fn replay(&self, req: SerializedRequest) -> Result<Response, TransportError> {
+        let next = self.calls.lock().unwrap_or_else(PoisonError::into_inner).pop_front();
+        let Some(call) = next else {
+            return Err(TransportErrorKind::custom_str(&format!(
+                "no recorded call left for request with id {id} and method {method}",
+                id = req.id(),
+                method = req.method()
+            )));
+        };
+        assert_eq!(call.method, req.method(), "replayed method does not match the recording");
+        Ok(Response { id: req.id().clone(), payload: call.response })
+    }
+
+    fn handle(&self, req: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        Ok(match req {
+            RequestPacket::Single(req) => ResponsePacket::Single(self.replay(req)?),
+            RequestPacket::Batch(reqs) => ResponsePacket::Batch(
+                reqs.into_iter().map(|req| self.replay(req)).collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+impl Service<RequestPacket> for ReplayTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let resp = self.handle(req);
+        Box::pin(async move { resp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RpcClient;
+    use alloy_primitives::U64;
+    use alloy_transport::mock::{Asserter, MockTransport};
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let asserter = Asserter::new();
+        asserter.push_success(&U64::from(1));
+        asserter.push_success(&"0x10");
+        let recorder = RecordingTransport::new(MockTransport::new(asserter));
+        let client = RpcClient::new(recorder.clone(), true);
+
+        let n: U64 = client.request_noparams("eth_blockNumber").await.unwrap();
+        let balance: String = client
+            .request("eth_getBalance", ("0x0000000000000000000000000000000000000000", "latest"))
+            .await
+            .unwrap();
+
+        let recording = recorder.into_recording();
+        assert_eq!(recording.len(), 2);
+        assert_eq!(recording[0].method, "eth_blockNumber");
+        assert_eq!(recording[1].method, "eth_getBalance");
+        assert!(recording[1].params.is_some());
+
+        let replay = ReplayTransport::from_recording(recording);
+        let client = RpcClient::new(replay.clone(), true);
+        assert_eq!(client.request_noparams::<U64>("eth_blockNumber").await.unwrap(), n);
+        let replayed: String = client.request("eth_getBalance", ((),)).await.unwrap();
+        assert_eq!(replayed, balance);
+        assert_eq!(replay.remaining(), 0);
+        assert!(client.request_noparams::<U64>("eth_blockNumber").await.is_err());
+    }
+}
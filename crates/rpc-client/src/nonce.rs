@@ -0,0 +1,224 @@
+use crate::RpcClient;
+use alloy_json_rpc::RpcError;
+use alloy_primitives::{Address, Bytes, B256, U64};
+use alloy_transport::{TransportError, TransportResult};
+use dashmap::DashMap;
+use std::{
+    ops::Deref,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// An [`RpcClient`] that tracks the next nonce of each sender address.
+///
+/// Nonces are fetched with `eth_getTransactionCount` the first time an address
+/// is used. [`next_nonce`] then reserves each nonce as it is handed out, so
+/// concurrent callers get distinct nonces. If sending the transaction with
+/// [`send_raw_transaction`] fails, its nonce is handed back. When the node
+/// rejects a transaction with a "nonce too low" error, the nonce is
+/// re-fetched.
+///
+/// Clones share the same nonces. Dereferences to the inner [`RpcClient`].
+///
+/// Created by [`RpcClient::with_auto_nonce_management`].
+///
+/// [`next_nonce`]: NonceManagingClient::next_nonce
+/// [`send_raw_transaction`]: NonceManagingClient::send_raw_transaction
+#[derive(Clone, Debug)]
+pub struct NonceManagingClient {
+    client: RpcClient,
+    nonces: Arc<DashMap<Address, AtomicU64>>,
+}
+
+impl RpcClient {
+    /// Wrap the client in a [`NonceManagingClient`], which tracks the next
+    /// nonce of each sender address.
+    pub fn with_auto_nonce_management(self) -> NonceManagingClient {
+        NonceManagingClient { client: self, nonces: Default::default() }
+    }
+}
+
+impl NonceManagingClient {
+    /// Get a reference to the inner client.
+    pub const fn client(&self) -> &RpcClient {
+        &self.client
+    }
+
+    /// Returns the tracked next nonce of the address, or `None` if the
+    /// address is not tracked yet.
+    pub fn current_nonce(&self, address: Address) -> Option<u64> {
+        self.nonces.get(&address).map(|nonce| nonce.load(Ordering::Acquire))
+    }
+
+    /// Reserve the next nonce of the address, fetching it from the node if
+    /// the address is not tracked yet.
+    ///
+    /// Each call returns a different nonce. If the nonce ends up unused, hand
+    /// it back with [`release_nonce`](Self::release_nonce).
+    pub async fn next_nonce(&self, address: Address) -> TransportResult<u64> {
+        if let Some(nonce) = self.nonces.get(&address) {
+            return Ok(nonce.fetch_add(1, Ordering::AcqRel));
+        }
+        let fetched = self.fetch_nonce(address).await?;
+        // Another caller may have started tracking the address meanwhile.
+        let nonce = self.nonces.entry(address).or_insert_with(|| AtomicU64::new(fetched));
+        Ok(nonce.fetch_add(1, Ordering::AcqRel))
+    }
+
+    /// Hand back a nonce reserved with [`next_nonce`](Self::next_nonce) that
+    /// was not used.
+    ///
+    /// The nonce is only reused if no later nonce of the address was reserved
+    /// since. Otherwise the gap stays, until the nonce is
+    /// [refreshed](Self::refresh_nonce).
+    pub fn release_nonce(&self, address: Address, nonce: u64) {
+        if let Some(next) = self.nonces.get(&address) {
+            let _ = next.compare_exchange(nonce + 1, nonce, Ordering::AcqRel, Ordering::Acquire);
+        }
+    }
+
+    /// Fetch the next nonce of the address from the node, including pending
+    /// transactions, and track it.
+    pub async fn refresh_nonce(&self, address: Address) -> TransportResult<u64> {
+        let nonce = self.fetch_nonce(address).await?;
+        // Store into the existing entry, so that concurrent reservations are
+        // not lost in a replaced one.
+        self.nonces.entry(address).or_default().store(nonce, Ordering::Release);
+        Ok(nonce)
+    }
+
+    async fn fetch_nonce(&self, address: Address) -> TransportResult<u64> {
+        let nonce: U64 =
+            self.client.request("eth_getTransactionCount", (address, "pending")).await?;
+        Ok(nonce.to())
+    }
+
+    /// Send a signed transaction of the given sender with
+    /// `eth_sendRawTransaction`, returning the transaction hash.
+    ///
+    /// `nonce` is the nonce of the transaction, as reserved with
+    /// [`next_nonce`](Self::next_nonce). If sending fails, the nonce is
+    /// [released](Self::release_nonce). If the node rejects the transaction
+    /// because its nonce is too low, the nonce is re-fetched instead, before
+    /// the error is returned.
+    pub async fn send_raw_transaction(
+        &self,
+        from: Address,
+        nonce: u64,
+        tx: Bytes,
+    ) -> TransportResult<B256> {
+        match self.client.request("eth_sendRawTransaction", (tx,)).await {
+            Ok(hash) => Ok(hash),
+            Err(err) if is_nonce_too_low(&err) => {
+                debug!(%from, "nonce too low, re-fetching nonce");
+                self.refresh_nonce(from).await?;
+                Err(err)
+            }
+            Err(err) => {
+                self.release_nonce(from, nonce);
+                Err(err)
+            }
+        }
+    }
+}
+
+impl Deref for NonceManagingClient {
+    type Target = RpcClient;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+/// Returns `true` if the node rejected a transaction because of its nonce.
+fn is_nonce_too_low(err: &TransportError) -> bool {
+    match err {
+        RpcError::ErrorResp(payload) => payload.message.to_lowercase().contains("nonce too low"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::ErrorPayload;
+    use alloy_transport::mock::Asserter;
+
+    #[tokio::test]
+    async fn tracks_nonces() {
+        let asserter = Asserter::new();
+        let client = RpcClient::mocked(asserter.clone()).with_auto_nonce_management();
+        let from = Address::with_last_byte(1);
+        assert_eq!(client.current_nonce(from), None);
+
+        asserter.push_success(&U64::from(5));
+        assert_eq!(client.next_nonce(from).await.unwrap(), 5);
+        assert_eq!(client.current_nonce(from), Some(6));
+
+        asserter.push_success(&B256::ZERO);
+        client.send_raw_transaction(from, 5, Bytes::new()).await.unwrap();
+        assert_eq!(client.current_nonce(from), Some(6));
+
+        let nonce = client.next_nonce(from).await.unwrap();
+        asserter.push_failure(ErrorPayload::new(-32000, "nonce too low"));
+        asserter.push_success(&U64::from(8));
+        client.send_raw_transaction(from, nonce, Bytes::new()).await.unwrap_err();
+        assert_eq!(client.current_nonce(from), Some(8));
+    }
+
+    #[tokio::test]
+    async fn concurrent_nonces() {
+        let asserter = Asserter::new();
+        let client = RpcClient::mocked(asserter.clone()).with_auto_nonce_management();
+        let from = Address::with_last_byte(1);
+
+        // Both callers fetch the nonce, but only one starts tracking it.
+        asserter.push_success(&U64::from(5));
+        asserter.push_success(&U64::from(5));
+        let (a, b) = tokio::join!(client.next_nonce(from), client.next_nonce(from));
+        let mut nonces = [a.unwrap(), b.unwrap()];
+        nonces.sort_unstable();
+        assert_eq!(nonces, [5, 6]);
+
+        let (a, b) = tokio::join!(client.next_nonce(from), client.next_nonce(from));
+        let mut nonces = [a.unwrap(), b.unwrap()];
+        nonces.sort_unstable();
+        assert_eq!(nonces, [7, 8]);
+    }
+
+    #[tokio::test]
+    async fn releases_nonce_on_failure() {
+        let asserter = Asserter::new();
+        let client = RpcClient::mocked(asserter.clone()).with_auto_nonce_management();
+        let from = Address::with_last_byte(1);
+
+        asserter.push_success(&U64::from(5));
+        let nonce = client.next_nonce(from).await.unwrap();
+        asserter.push_failure(ErrorPayload::internal_error());
+        client.send_raw_transaction(from, nonce, Bytes::new()).await.unwrap_err();
+        assert_eq!(client.next_nonce(from).await.unwrap(), 5);
+
+        // Nonces reserved later are not handed out twice.
+        let later = client.next_nonce(from).await.unwrap();
+        client.release_nonce(from, 5);
+        assert_eq!(later, 6);
+        assert_eq!(client.current_nonce(from), Some(7));
+    }
+
+    #[tokio::test]
+    async fn refresh_nonce() {
+        let asserter = Asserter::new();
+        let client = RpcClient::mocked(asserter.clone()).with_auto_nonce_management();
+        let from = Address::with_last_byte(1);
+
+        asserter.push_success(&U64::from(5));
+        client.next_nonce(from).await.unwrap();
+
+        asserter.push_success(&U64::from(9));
+        assert_eq!(client.refresh_nonce(from).await.unwrap(), 9);
+        assert_eq!(client.next_nonce(from).await.unwrap(), 9);
+        assert_eq!(client.current_nonce(from), Some(10));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::RpcClient;
+use alloy_json_rpc::{RpcRecv, RpcSend};
+use alloy_transport::TransportResult;
+use std::{
+    borrow::Cow,
+    hash::{BuildHasher, RandomState},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The strategy used by an [`RpcClientPool`] to select a client.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PoolStrategy {
+    /// Cycle through the clients in order.
+    #[default]
+    RoundRobin,
+    /// Select the client with the fewest requests in flight.
+    ///
+    /// Only requests dispatched via [`RpcClientPool::call`] are counted.
+    LeastBusy,
+    /// Select a client at random.
+    Random,
+}
+
+/// A pooled client, along with the number of requests in flight on it.
+#[derive(Debug)]
+struct PooledClient {
+    client: RpcClient,
+    in_flight: AtomicUsize,
+}
+
+/// A pool of identical [`RpcClient`]s.
+///
+/// Spreading requests over several connections to the same node avoids a
+/// single connection becoming the bottleneck, e.g. when large
+/// `debug_traceBlock` responses saturate a WebSocket connection.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_rpc_client::{ClientBuilder, PoolStrategy, RpcClientPool};
+///
+/// let mut clients = Vec::new();
+/// for _ in 0..4 {
+///     clients.push(ClientBuilder::default().connect("ws://localhost:8545").await?);
+/// }
+/// let pool = RpcClientPool::new(clients, PoolStrategy::LeastBusy);
+///
+/// let block_number: alloy_primitives::U64 = pool.call("eth_blockNumber", ()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RpcClientPool {
+    clients: Vec<PooledClient>,
+    strategy: PoolStrategy,
+    /// Counter used for round-robin and random selection.
+    next: AtomicUsize,
+    /// Hasher used for random selection.
+    random: RandomState,
+}
+
+impl RpcClientPool {
+    /// Create a new pool from the given clients, selecting clients with the
+    /// given strategy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `clients` is empty.
+    pub fn new(clients: Vec<RpcClient>, strategy: PoolStrategy) -> Self {
+        assert!(!clients.is_empty(), "RpcClientPool requires at least one client");
+        Self {
+            clients: clients
+                .into_iter()
+                .map(|client| PooledClient { client, in_flight: AtomicUsize::new(0) })
+                .collect(),
+            strategy,
+            next: AtomicUsize::new(0),
+            random: RandomState::new(),
+        }
+    }
+
+    /// Get the selection strategy of the pool.
+    pub const fn strategy(&self) -> PoolStrategy {
+        self.strategy
+    }
+
+    /// Get the number of clients in the pool.
+    pub const fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Returns `true` if the pool has no clients. This is never the case, as
+    /// pools are created with at least one client.
+    pub const fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    /// Get an iterator over the clients in the pool.
+    pub fn clients(&self) -> impl Iterator<Item = &RpcClient> {
+        self.clients.iter().map(|pooled| &pooled.client)
+    }
+
+    /// Get the next client, according to the pool's strategy.
+    pub fn get(&self) -> &RpcClient {
+        &self.clients[self.select()].client
+    }
+
+    /// Send a request to the next client, according to the pool's strategy,
+    /// and wait for its response.
+    pub async fn call<Params: RpcSend, Resp: RpcRecv>(
+        &self,
+        method: impl Into<Cow<'static, str>>,
+        params: Params,
+    ) -> TransportResult<Resp> {
+        let pooled = &self.clients[self.select()];
+        pooled.in_flight.fetch_add(1, Ordering::Relaxed);
+        let _guard = InFlightGuard(&pooled.in_flight);
+        pooled.client.request(method, params).await
+    }
+
+    /// Select the index of the next client.
+    fn select(&self) -> usize {
+        match self.strategy {
+            PoolStrategy::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed) % self.len(),
+            PoolStrategy::LeastBusy => self
+                .clients
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, pooled)| pooled.in_flight.load(Ordering::Relaxed))
+                .map(|(idx, _)| idx)
+                .unwrap_or_default(),
+            PoolStrategy::Random => {
+                let n = self.next.fetch_add(1, Ordering::Relaxed);
+                self.random.hash_one(n) as usize % self.len()
+            }
+        }
+    }
+}
+
+/// Decrements the in-flight counter of a pooled client when dropped, so that
+/// cancelled calls are accounted for.
+struct InFlightGuard<'a>(&'a AtomicUsize);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U64;
+    use alloy_transport::mock::Asserter;
+
+    #[tokio::test]
+    async fn round_robin() {
+        let asserters = [Asserter::new(), Asserter::new()];
+        let clients = asserters.iter().cloned().map(RpcClient::mocked).collect();
+        let pool = RpcClientPool::new(clients, PoolStrategy::RoundRobin);
+
+        asserters[0].push_success(&U64::from(1));
+        asserters[1].push_success(&U64::from(2));
+        asserters[0].push_success(&U64::from(3));
+
+        for expected in 1..=3 {
+            let n: U64 = pool.call("eth_blockNumber", ()).await.unwrap();
+            assert_eq!(n, U64::from(expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn least_busy() {
+        let clients = (0..3).map(|_| RpcClient::mocked(Asserter::new())).collect();
+        let pool = RpcClientPool::new(clients, PoolStrategy::LeastBusy);
+
+        pool.clients[0].in_flight.store(2, Ordering::Relaxed);
+        pool.clients[1].in_flight.store(1, Ordering::Relaxed);
+        pool.clients[2].in_flight.store(3, Ordering::Relaxed);
+        assert_eq!(pool.select(), 1);
+    }
+}
@@ -0,0 +1,534 @@
+use crate::{RpcClient, RpcClientInner, WeakClient};
+use alloy_json_rpc::RpcError;
+use alloy_primitives::{TxHash, B256};
+use alloy_pubsub::{RawSubscription, Subscription, SubscriptionStream};
+use alloy_transport::{utils::Spawnable, BoxFuture, TransportErrorKind, TransportResult};
+use futures::{FutureExt, Stream, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::value::{to_raw_value, RawValue};
+use std::{
+    borrow::Cow,
+    fmt,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// A builder for `eth_subscribe` subscriptions with optional server-side
+/// filter parameters.
+///
+/// ```no_run
+/// # async fn example(client: alloy_rpc_client::RpcClient) -> alloy_transport::TransportResult<()> {
+/// use alloy_rpc_client::SubscriptionBuilder;
+/// use serde_json::json;
+///
+/// let filter = json!({ "address": "0xdAC17F958D2ee523a2206206994597C13D831ec7" });
+/// let mut sub = SubscriptionBuilder::new("logs")
+///     .with_params(filter)
+///     .subscribe::<serde_json::Value>(&client)
+///     .await?;
+/// let log = sub.recv().await.unwrap();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SubscriptionBuilder<P = ()> {
+    sub_type: Cow<'static, str>,
+    params: Option<P>,
+}
+
+impl SubscriptionBuilder {
+    /// Create a new subscription builder for the given subscription type,
+    /// e.g. `"newHeads"` or `"logs"`.
+    pub fn new(sub_type: impl Into<Cow<'static, str>>) -> Self {
+        Self { sub_type: sub_type.into(), params: None }
+    }
+}
+
+impl<P> SubscriptionBuilder<P> {
+    /// Set the filter parameters of the subscription.
+    ///
+    /// These are sent as the second element of the `eth_subscribe` params.
+    pub fn with_params<Q: Serialize>(self, params: Q) -> SubscriptionBuilder<Q> {
+        SubscriptionBuilder { sub_type: self.sub_type, params: Some(params) }
+    }
+
+    /// Issue the `eth_subscribe` request on the given client, returning a
+    /// [`FilteredSubscription`] that remembers the subscription type and filter.
+    pub async fn subscribe<T>(self, client: &RpcClient) -> TransportResult<FilteredSubscription<T>>
+    where
+        P: Serialize,
+    {
+        let params =
+            self.params.map(|p| to_raw_value(&p)).transpose().map_err(RpcError::ser_err)?;
+        let inner = subscribe_raw(client, &self.sub_type, params.as_deref()).await?;
+        Ok(FilteredSubscription {
+            client: client.get_weak(),
+            sub_type: self.sub_type,
+            params,
+            inner,
+        })
+    }
+}
+
+/// A [`Subscription`] that carries the subscription type and filter it was
+/// created with.
+///
+/// The pubsub service already re-issues active subscriptions when the
+/// underlying connection is re-established. [`resubscribe`] can be used to
+/// re-issue the subscription manually with the same filter, e.g. after the
+/// server dropped it.
+///
+/// Dereferences to the inner [`Subscription`].
+///
+/// [`resubscribe`]: FilteredSubscription::resubscribe
+pub struct FilteredSubscription<T> {
+    client: WeakClient,
+    sub_type: Cow<'static, str>,
+    params: Option<Box<RawValue>>,
+    inner: Subscription<T>,
+}
+
+impl<T> fmt::Debug for FilteredSubscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilteredSubscription")
+            .field("sub_type", &self.sub_type)
+            .field("params", &self.params)
+            .field("local_id", self.inner.local_id())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> FilteredSubscription<T> {
+    /// Get the subscription type, e.g. `"logs"`.
+    pub fn sub_type(&self) -> &str {
+        &self.sub_type
+    }
+
+    /// Get the serialized filter parameters, if any.
+    pub fn params(&self) -> Option<&RawValue> {
+        self.params.as_deref()
+    }
+
+    /// Get a reference to the inner subscription.
+    pub const fn inner(&self) -> &Subscription<T> {
+        &self.inner
+    }
+
+    /// Consume the filtered subscription, returning the inner subscription.
+    pub fn into_inner(self) -> Subscription<T> {
+        self.inner
+    }
+
+    /// Re-issue the `eth_subscribe` request with the original subscription
+    /// type and filter, replacing the inner subscription.
+    ///
+    /// The previous subscription is unsubscribed.
+    pub async fn resubscribe(&mut self) -> TransportResult<()> {
+        let client = self
+            .client
+            .upgrade()
+            .ok_or_else(|| TransportErrorKind::custom_str("client dropped"))?;
+        let inner = subscribe_raw(&client, &self.sub_type, self.params.as_deref()).await?;
+        let old = std::mem::replace(&mut self.inner, inner);
+
+        let pubsub = client.pubsub_frontend().ok_or(TransportErrorKind::PubsubUnavailable)?;
+        pubsub.unsubscribe(*old.local_id())
+    }
+
+    /// Convert into a [`ReconnectingSubscription`], which re-issues the
+    /// subscription after it ended and the connection was re-established.
+    pub fn into_reconnecting(self) -> ReconnectingSubscription<T>
+    where
+        T: DeserializeOwned,
+    {
+        let since = reconnect_count(&self.client);
+        ReconnectingSubscription {
+            client: self.client,
+            sub_type: self.sub_type,
+            params: self.params,
+            state: ReconnectState::Active(self.inner.into_stream(), since),
+        }
+    }
+}
+
+impl<T> Deref for FilteredSubscription<T> {
+    type Target = Subscription<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for FilteredSubscription<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl RpcClient {
+    /// Subscribe to the given subscription type with server-side filter
+    /// parameters.
+    ///
+    /// See [`SubscriptionBuilder`] for more details.
+    pub async fn subscribe_with_filter<T>(
+        &self,
+        sub_type: impl Into<Cow<'static, str>>,
+        params: impl Serialize,
+    ) -> TransportResult<FilteredSubscription<T>> {
+        SubscriptionBuilder::new(sub_type).with_params(params).subscribe(self).await
+    }
+
+    /// Subscribe to the given subscription type with server-side filter
+    /// parameters, re-issuing the subscription after the connection was
+    /// re-established.
+    ///
+    /// See [`ReconnectingSubscription`] for more details.
+    pub async fn subscribe_with_reconnect<T: DeserializeOwned>(
+        &self,
+        sub_type: impl Into<Cow<'static, str>>,
+        params: impl Serialize,
+    ) -> TransportResult<ReconnectingSubscription<T>> {
+        self.subscribe_with_filter(sub_type, params)
+            .await
+            .map(FilteredSubscription::into_reconnecting)
+    }
+
+    /// Subscribe to `newPendingTransactions`, yielding only the transaction
+    /// hashes for which `predicate` returns `true`.
+    ///
+    /// The predicate is applied client-side by a background task, which only
+    /// forwards the matching hashes to the returned subscription, so that
+    /// other hashes don't fill its channel. The task stops once the returned
+    /// subscription, and all of its resubscriptions, are dropped.
+    pub async fn subscribe_new_pending_transactions_filtered(
+        &self,
+        predicate: impl Fn(&TxHash) -> bool + Send + Sync + 'static,
+    ) -> TransportResult<Subscription<TxHash>> {
+        let sub: Subscription<TxHash> = subscribe_raw(self, "newPendingTransactions", None).await?;
+        let local_id = *sub.local_id();
+        let mut inner = sub.into_raw();
+        let (tx, rx) = broadcast::channel(self.channel_size());
+        async move {
+            loop {
+                let notification = match inner.rx.recv().await {
+                    Ok(notification) => notification,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+                if tx.receiver_count() == 0 {
+                    break;
+                }
+                let matches = serde_json::from_str(notification.value.get())
+                    .is_ok_and(|hash: TxHash| predicate(&hash));
+                if matches && tx.send(notification).is_err() {
+                    break;
+                }
+            }
+        }
+        .spawn_task();
+        Ok(RawSubscription { rx, local_id }.into())
+    }
+}
+
+/// A stream of subscription items that survives reconnections, re-issuing its
+/// `eth_subscribe` request with the original subscription type and filter if
+/// needed.
+///
+/// The pubsub service already re-issues active subscriptions when the
+/// underlying connection is re-established, keeping the stream alive. If the
+/// subscription ends anyway, e.g. because the server rejected it after a
+/// reconnection, this stream waits for the next reconnection, and then
+/// re-issues `eth_subscribe`. Items sent in the meantime are missed.
+///
+/// The stream ends if re-subscribing fails, or if the client was dropped or
+/// the connection could not be re-established.
+///
+/// Created by [`RpcClient::subscribe_with_reconnect`] or
+/// [`FilteredSubscription::into_reconnecting`].
+pub struct ReconnectingSubscription<T> {
+    client: WeakClient,
+    sub_type: Cow<'static, str>,
+    params: Option<Box<RawValue>>,
+    state: ReconnectState<T>,
+}
+
+/// The stream of a [`ReconnectingSubscription`], and the reconnection count
+/// when it was subscribed.
+type Resubscribed<T> = (SubscriptionStream<T>, u32);
+
+enum ReconnectState<T> {
+    Active(SubscriptionStream<T>, u32),
+    WaitingForReconnect(BoxFuture<'static, bool>),
+    Resubscribing(BoxFuture<'static, TransportResult<Resubscribed<T>>>),
+    Done,
+}
+
+impl<T> fmt::Debug for ReconnectingSubscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match &self.state {
+            ReconnectState::Active(..) => "active",
+            ReconnectState::WaitingForReconnect(_) => "waiting for reconnect",
+            ReconnectState::Resubscribing(_) => "resubscribing",
+            ReconnectState::Done => "done",
+        };
+        f.debug_struct("ReconnectingSubscription")
+            .field("sub_type", &self.sub_type)
+            .field("params", &self.params)
+            .field("state", &state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> ReconnectingSubscription<T> {
+    /// Get the subscription type, e.g. `"logs"`.
+    pub fn sub_type(&self) -> &str {
+        &self.sub_type
+    }
+
+    /// Get the serialized filter parameters, if any.
+    pub fn params(&self) -> Option<&RawValue> {
+        self.params.as_deref()
+    }
+
+    /// Returns the future waiting for a reconnection after `since`, or `None`
+    /// if the client was dropped.
+    fn wait_for_reconnect(&self, since: u32) -> Option<BoxFuture<'static, bool>> {
+        let client = self.client.upgrade()?;
+        Some(client.pubsub_frontend()?.wait_for_reconnect(since).boxed())
+    }
+
+    /// Returns the future re-issuing the subscription, or `None` if the
+    /// client was dropped.
+    fn resubscribe(&self) -> Option<BoxFuture<'static, TransportResult<Resubscribed<T>>>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let client = self.client.upgrade()?;
+        let sub_type = self.sub_type.clone();
+        let params = self.params.clone();
+        Some(
+            async move {
+                let since = client.pubsub_frontend().map_or(0, |pubsub| pubsub.reconnect_count());
+                let sub: Subscription<T> =
+                    subscribe_raw(&client, &sub_type, params.as_deref()).await?;
+                Ok((sub.into_stream(), since))
+            }
+            .boxed(),
+        )
+    }
+}
+
+impl<T: DeserializeOwned + 'static> Stream for ReconnectingSubscription<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match &mut self.state {
+                ReconnectState::Active(stream, since) => {
+                    let since = *since;
+                    match ready!(stream.poll_next_unpin(cx)) {
+                        Some(item) => return Poll::Ready(Some(item)),
+                        None => {
+                            debug!(
+                                sub_type = %self.sub_type,
+                                "subscription ended, waiting for reconnect"
+                            );
+                            self.state = self
+                                .wait_for_reconnect(since)
+                                .map_or(ReconnectState::Done, ReconnectState::WaitingForReconnect);
+                        }
+                    }
+                }
+                ReconnectState::WaitingForReconnect(fut) => {
+                    self.state = if ready!(fut.poll_unpin(cx)) {
+                        debug!(sub_type = %self.sub_type, "reconnected, resubscribing");
+                        self.resubscribe()
+                            .map_or(ReconnectState::Done, ReconnectState::Resubscribing)
+                    } else {
+                        ReconnectState::Done
+                    };
+                }
+                ReconnectState::Resubscribing(fut) => match ready!(fut.poll_unpin(cx)) {
+                    Ok((stream, since)) => self.state = ReconnectState::Active(stream, since),
+                    Err(err) => {
+                        warn!(%err, sub_type = %self.sub_type, "failed to resubscribe");
+                        self.state = ReconnectState::Done;
+                    }
+                },
+                ReconnectState::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Returns the reconnection count of the pubsub frontend of the client.
+fn reconnect_count(client: &WeakClient) -> u32 {
+    client
+        .upgrade()
+        .and_then(|client| client.pubsub_frontend().map(|pubsub| pubsub.reconnect_count()))
+        .unwrap_or_default()
+}
+
+async fn subscribe_raw<T>(
+    client: &RpcClientInner,
+    sub_type: &str,
+    params: Option<&RawValue>,
+) -> TransportResult<Subscription<T>> {
+    let pubsub = client.pubsub_frontend().ok_or(TransportErrorKind::PubsubUnavailable)?;
+    let id: B256 = match params {
+        Some(params) => {
+            client.request("eth_subscribe", (sub_type.to_owned(), params.to_owned())).await?
+        }
+        None => client.request("eth_subscribe", (sub_type.to_owned(),)).await?,
+    };
+    pubsub.get_subscription(id).await.map(Subscription::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientBuilder;
+    use alloy_json_rpc::{EthNotification, Id, PubSubItem, Response, ResponsePayload, SubId};
+    use alloy_primitives::U256;
+    use alloy_pubsub::{mock::MockConnect, ConnectionInterface};
+    use serde_json::{json, Value};
+    use std::time::Duration;
+    use tokio::task::JoinHandle;
+
+    /// Receive the next request from the frontend, skipping
+    /// `eth_unsubscribe` requests.
+    async fn next_subscribe(interface: &mut ConnectionInterface) -> Value {
+        loop {
+            let req = interface.recv_from_frontend().await.unwrap();
+            let req: Value = serde_json::from_str(req.get()).unwrap();
+            if req["method"] != "eth_unsubscribe" {
+                assert_eq!(req["method"], "eth_subscribe");
+                return req;
+            }
+        }
+    }
+
+    /// Answer an `eth_subscribe` request with the given server ID.
+    fn respond(interface: &ConnectionInterface, req: &Value, server_id: u64) {
+        let id: Id = serde_json::from_value(req["id"].clone()).unwrap();
+        let payload = ResponsePayload::Success(to_raw_value(&U256::from(server_id)).unwrap());
+        interface.send_to_frontend(PubSubItem::Response(Response { id, payload })).unwrap();
+    }
+
+    fn notify(interface: &ConnectionInterface, server_id: u64, item: impl Serialize) {
+        let notification = EthNotification {
+            subscription: SubId::Number(U256::from(server_id)),
+            result: to_raw_value(&item).unwrap(),
+        };
+        let _ = interface.send_to_frontend(PubSubItem::Notification(notification));
+    }
+
+    /// Notify until the spawned `next` call finishes, as the subscription
+    /// may not be registered yet.
+    async fn notify_until<R>(
+        interface: &ConnectionInterface,
+        server_id: u64,
+        item: u64,
+        next: JoinHandle<R>,
+    ) -> R {
+        while !next.is_finished() {
+            notify(interface, server_id, item);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        next.await.unwrap()
+    }
+
+    /// Subscribe to `logs` with a filter, answering with server ID 1.
+    async fn subscribe(
+        client: &RpcClient,
+        interface: &mut ConnectionInterface,
+    ) -> FilteredSubscription<u64> {
+        let filter = json!({ "address": "0x01" });
+        let (sub, req) =
+            tokio::join!(client.subscribe_with_filter::<u64>("logs", &filter), async {
+                let req = next_subscribe(interface).await;
+                respond(interface, &req, 1);
+                req
+            });
+        assert_eq!(req["params"], json!(["logs", filter]));
+        sub.unwrap()
+    }
+
+    #[tokio::test]
+    async fn filtered_pending_transactions() {
+        let (connect, mut interfaces) = MockConnect::new();
+        let client = ClientBuilder::default().pubsub(connect).await.unwrap();
+        let mut interface = interfaces.recv().await.unwrap();
+
+        let (sub, req) = tokio::join!(
+            client.subscribe_new_pending_transactions_filtered(|hash| hash[0] == 1),
+            async {
+                let req = next_subscribe(&mut interface).await;
+                respond(&interface, &req, 1);
+                req
+            }
+        );
+        assert_eq!(req["params"], json!(["newPendingTransactions"]));
+        let mut sub = sub.unwrap();
+
+        for byte in [2, 1, 3, 1] {
+            notify(&interface, 1, TxHash::repeat_byte(byte));
+        }
+        assert_eq!(sub.recv().await.unwrap(), TxHash::repeat_byte(1));
+        assert_eq!(sub.recv().await.unwrap(), TxHash::repeat_byte(1));
+        assert!(sub.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reconnecting_subscription_survives_reconnect() {
+        let (connect, mut interfaces) = MockConnect::new();
+        let client = ClientBuilder::default().pubsub(connect).await.unwrap();
+        let mut interface = interfaces.recv().await.unwrap();
+        let mut sub = subscribe(&client, &mut interface).await.into_reconnecting();
+
+        notify(&interface, 1, 10);
+        assert_eq!(sub.next().await, Some(10));
+
+        // The service re-issues the subscription on the new connection.
+        interface.close_with_error();
+        let mut interface = interfaces.recv().await.unwrap();
+        let req = next_subscribe(&mut interface).await;
+        assert_eq!(req["params"], json!(["logs", { "address": "0x01" }]));
+        respond(&interface, &req, 2);
+
+        let next = tokio::spawn(async move { (sub.next().await, sub) });
+        let (item, sub) = notify_until(&interface, 2, 20, next).await;
+        assert_eq!(item, Some(20));
+        assert!(matches!(sub.state, ReconnectState::Active(..)));
+    }
+
+    #[tokio::test]
+    async fn reconnecting_subscription_resubscribes_after_reconnect() {
+        let (connect, mut interfaces) = MockConnect::new();
+        let client = ClientBuilder::default().pubsub(connect).await.unwrap();
+        let mut interface = interfaces.recv().await.unwrap();
+        let sub = subscribe(&client, &mut interface).await;
+        let local_id = *sub.local_id();
+        let mut sub = sub.into_reconnecting();
+
+        // Removing the subscription ends its stream, which then waits for a
+        // reconnection.
+        client.pubsub_frontend().unwrap().unsubscribe(local_id).unwrap();
+        let next = tokio::spawn(async move { (sub.next().await, sub) });
+        let res = tokio::time::timeout(Duration::from_millis(50), next_subscribe(&mut interface));
+        assert!(res.await.is_err(), "resubscribed before reconnecting");
+
+        // After reconnecting, `eth_subscribe` is re-issued with the same filter.
+        interface.close_with_error();
+        let mut interface = interfaces.recv().await.unwrap();
+        let req = next_subscribe(&mut interface).await;
+        assert_eq!(req["params"], json!(["logs", { "address": "0x01" }]));
+        respond(&interface, &req, 2);
+
+        let (item, sub) = notify_until(&interface, 2, 20, next).await;
+        assert_eq!(item, Some(20));
+        assert_eq!(sub.sub_type(), "logs");
+    }
+}
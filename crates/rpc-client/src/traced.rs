@@ -0,0 +1,150 @@
+use crate::{RpcCall, RpcClient};
+use alloy_json_rpc::{RpcRecv, RpcSend};
+use alloy_transport::{RpcFut, TransportErrorKind, TransportResult};
+use std::{borrow::Cow, future::IntoFuture};
+use tracing::{Instrument, Span};
+
+#[cfg(target_family = "wasm")]
+use wasmtimer::{std::Instant, tokio::timeout};
+
+#[cfg(not(target_family = "wasm"))]
+use {std::time::Instant, tokio::time::timeout};
+
+/// A request with per-call tracing configuration, created by
+/// [`RpcClient::trace_request`].
+///
+/// The call is sent when the builder is awaited, or with
+/// [`send`](Self::send). It runs in an `rpc_request` span recording the
+/// method, request ID and trace ID, whose parent is the span set with
+/// [`with_span`](Self::with_span), if any.
+#[must_use = "requests do nothing unless you `.await` or `.send()` them"]
+#[derive(Debug)]
+pub struct TracedCall<Params: RpcSend, Resp> {
+    call: RpcCall<Params, Resp>,
+    span: Option<Span>,
+    deadline: Option<Instant>,
+    trace_id: Option<u128>,
+}
+
+impl RpcClient {
+    /// Prepare a request with per-call tracing configuration.
+    ///
+    /// See [`TracedCall`] for more details.
+    pub fn trace_request<Params: RpcSend, Resp: RpcRecv>(
+        &self,
+        method: impl Into<Cow<'static, str>>,
+        params: Params,
+    ) -> TracedCall<Params, Resp> {
+        TracedCall {
+            call: self.request(method, params),
+            span: None,
+            deadline: None,
+            trace_id: None,
+        }
+    }
+}
+
+impl<Params: RpcSend, Resp> TracedCall<Params, Resp> {
+    /// Sets the span entered around the transport call.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Sets a deadline, after which the call is cancelled with a
+    /// [`TransportErrorKind::Timeout`] error.
+    pub const fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the trace ID recorded in the `rpc_request` span.
+    pub const fn with_trace_id(mut self, trace_id: u128) -> Self {
+        self.trace_id = Some(trace_id);
+        self
+    }
+
+    /// Returns the trace ID, if any.
+    pub const fn trace_id(&self) -> Option<u128> {
+        self.trace_id
+    }
+
+    /// Returns the underlying call.
+    pub const fn call(&self) -> &RpcCall<Params, Resp> {
+        &self.call
+    }
+}
+
+impl<Params, Resp> TracedCall<Params, Resp>
+where
+    Params: RpcSend + 'static,
+    Resp: RpcRecv,
+{
+    /// Send the request.
+    pub async fn send(self) -> TransportResult<Resp> {
+        let Self { call, span, deadline, trace_id } = self;
+        let span = span.unwrap_or_else(Span::current);
+        let request_span = debug_span!(
+            parent: &span,
+            "rpc_request",
+            method = call.method(),
+            id = %call.request().meta.id,
+            trace_id = trace_id.map(|id| format!("{id:032x}")),
+        );
+
+        let fut = async move {
+            let Some(deadline) = deadline else { return call.await };
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(TransportErrorKind::timeout());
+            }
+            timeout(remaining, call).await.unwrap_or_else(|_| Err(TransportErrorKind::timeout()))
+        };
+        fut.instrument(request_span).instrument(span).await
+    }
+}
+
+impl<Params, Resp> IntoFuture for TracedCall<Params, Resp>
+where
+    Params: RpcSend + 'static,
+    Resp: RpcRecv,
+{
+    type Output = TransportResult<Resp>;
+    type IntoFuture = RpcFut<'static, Resp>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U64;
+    use alloy_transport::mock::Asserter;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn traced_call() {
+        let asserter = Asserter::new();
+        asserter.push_success(&U64::from(1));
+        let client = RpcClient::mocked(asserter);
+
+        let n: U64 = client
+            .trace_request("eth_blockNumber", ())
+            .with_span(tracing::info_span!("test"))
+            .with_trace_id(7)
+            .with_deadline(Instant::now() + Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(n, U64::from(1));
+
+        let err = client
+            .trace_request::<_, U64>("eth_blockNumber", ())
+            .with_deadline(Instant::now() - Duration::from_secs(1))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.as_transport_err().is_some_and(TransportErrorKind::is_timeout));
+    }
+}
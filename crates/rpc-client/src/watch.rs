@@ -0,0 +1,147 @@
+use crate::{RpcClient, WeakClient};
+use alloy_primitives::U64;
+use alloy_transport::TransportResult;
+use futures::{stream, Stream};
+use serde::Deserialize;
+use std::time::Duration;
+
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::sleep;
+
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::sleep;
+
+/// The block number of a `newHeads` notification.
+#[derive(Clone, Debug, Deserialize)]
+#[cfg_attr(not(feature = "pubsub"), allow(dead_code))]
+struct HeadNumber {
+    number: U64,
+}
+
+/// The state of a [`RpcClient::watch_blocks`] stream.
+enum WatchState {
+    /// Nothing has been requested yet.
+    Start,
+    /// Receiving `newHeads` notifications.
+    #[cfg(feature = "pubsub")]
+    Subscribed(alloy_pubsub::SubscriptionStream<HeadNumber>),
+    /// Polling `eth_blockNumber`, with the last yielded block number.
+    Polling(Option<u64>),
+}
+
+impl RpcClient {
+    /// Returns a stream of new block numbers.
+    ///
+    /// If the transport supports subscriptions, the stream yields the number
+    /// of every `newHeads` notification. Otherwise, `eth_blockNumber` is
+    /// polled every `poll_interval`, and block numbers are yielded whenever
+    /// they increase. Polling is also used if the subscription can't be
+    /// created.
+    ///
+    /// Errors of individual polls are yielded without ending the stream. The
+    /// stream ends when the subscription ends, or when the client is dropped.
+    pub fn watch_blocks(
+        &self,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = TransportResult<u64>> {
+        let client = self.get_weak();
+        stream::unfold(WatchState::Start, move |state| {
+            next_block(client.clone(), state, poll_interval)
+        })
+    }
+}
+
+async fn next_block(
+    client: WeakClient,
+    mut state: WatchState,
+    poll_interval: Duration,
+) -> Option<(TransportResult<u64>, WatchState)> {
+    loop {
+        match state {
+            WatchState::Start => {
+                #[cfg(feature = "pubsub")]
+                if let Some(stream) = subscribe_new_heads(&client).await {
+                    state = WatchState::Subscribed(stream);
+                    continue;
+                }
+                // Poll immediately on start.
+                let number = poll_block_number(&client).await?;
+                return Some(advance(number, None));
+            }
+            #[cfg(feature = "pubsub")]
+            WatchState::Subscribed(mut stream) => {
+                let head = futures::StreamExt::next(&mut stream).await?;
+                return Some((Ok(head.number.to()), WatchState::Subscribed(stream)));
+            }
+            WatchState::Polling(last) => {
+                sleep(poll_interval).await;
+                match poll_block_number(&client).await? {
+                    Ok(number) if last.is_some_and(|last| number <= last) => {
+                        state = WatchState::Polling(last);
+                    }
+                    res => return Some(advance(res, last)),
+                }
+            }
+        }
+    }
+}
+
+/// Returns the next item and state of a polling stream.
+fn advance(res: TransportResult<u64>, last: Option<u64>) -> (TransportResult<u64>, WatchState) {
+    let last = res.as_ref().ok().copied().or(last);
+    (res, WatchState::Polling(last))
+}
+
+/// Requests the current block number, or returns `None` if the client was
+/// dropped.
+async fn poll_block_number(client: &WeakClient) -> Option<TransportResult<u64>> {
+    let client = client.upgrade()?;
+    let res: TransportResult<U64> = client.request_noparams("eth_blockNumber").await;
+    Some(res.map(|number| number.to()))
+}
+
+/// Subscribes to `newHeads`, or returns `None` if the transport does not
+/// support subscriptions or the subscription fails.
+#[cfg(feature = "pubsub")]
+async fn subscribe_new_heads(
+    client: &WeakClient,
+) -> Option<alloy_pubsub::SubscriptionStream<HeadNumber>> {
+    let client = client.upgrade()?;
+    let pubsub = client.pubsub_frontend()?;
+    let res = async {
+        let id: alloy_primitives::B256 = client.request("eth_subscribe", ("newHeads",)).await?;
+        pubsub.get_subscription(id).await
+    }
+    .await;
+    match res {
+        Ok(sub) => Some(alloy_pubsub::Subscription::from(sub).into_stream()),
+        Err(err) => {
+            debug!(%err, "failed to subscribe to new heads, falling back to polling");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_transport::mock::Asserter;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn watch_blocks_polling() {
+        let asserter = Asserter::new();
+        for number in [1u64, 1, 2, 2, 3] {
+            asserter.push_success(&U64::from(number));
+        }
+        let client = RpcClient::mocked(asserter);
+
+        let numbers: Vec<u64> = client
+            .watch_blocks(Duration::from_millis(1))
+            .take(3)
+            .map(|res| res.unwrap())
+            .collect()
+            .await;
+        assert_eq!(numbers, [1, 2, 3]);
+    }
+}
@@ -15,3 +15,21 @@ async fn it_makes_a_request() {
     let res = timeout.await.unwrap().unwrap();
     assert_eq!(res.to::<u64>(), 0);
 }
+
+#[tokio::test]
+async fn it_subscribes_with_filter() {
+    let anvil = Anvil::new().spawn();
+    let url = anvil.ws_endpoint();
+    let connector = WsConnect::new(url);
+    let client = ClientBuilder::default().pubsub(connector).await.unwrap();
+
+    let filter = serde_json::json!({ "address": anvil.addresses()[0] });
+    let mut sub =
+        client.subscribe_with_filter::<serde_json::Value>("logs", filter.clone()).await.unwrap();
+    assert_eq!(sub.sub_type(), "logs");
+    assert_eq!(sub.params().unwrap().get(), serde_json::to_string(&filter).unwrap());
+
+    let old_id = *sub.local_id();
+    sub.resubscribe().await.unwrap();
+    assert_ne!(*sub.local_id(), old_id);
+}
@@ -0,0 +1,45 @@
+use alloy_transport::BoxTransport;
+use url::Url;
+
+/// Creates HTTP transports with the backend best suited to the target.
+///
+/// This allows code that is compiled both natively and for WASM to create an
+/// HTTP transport without `cfg` blocks of its own.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct TransportFactory;
+
+impl TransportFactory {
+    /// Create a boxed HTTP transport for the given URL.
+    ///
+    /// This uses [`hyper`](crate::HyperTransport) when the `hyper` feature is
+    /// enabled and the target is not WASM, and
+    /// [`reqwest`](crate::ReqwestTransport) otherwise.
+    pub fn auto(url: Url) -> BoxTransport {
+        #[cfg(all(not(target_family = "wasm"), feature = "hyper"))]
+        {
+            BoxTransport::new(crate::HyperTransport::new_hyper(url))
+        }
+
+        #[cfg(not(all(not(target_family = "wasm"), feature = "hyper")))]
+        {
+            BoxTransport::new(crate::ReqwestTransport::new(url))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{request, serve_json_rpc};
+    use tower::Service;
+
+    #[tokio::test]
+    async fn auto() {
+        let url = serve_json_rpc().await;
+        let mut transport = TransportFactory::auto(url);
+
+        let resp = transport.call(request()).await.unwrap();
+        assert_eq!(resp.as_single().unwrap().payload.as_success().unwrap().get(), r#""0x1""#);
+    }
+}
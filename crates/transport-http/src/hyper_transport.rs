@@ -1,46 +1,163 @@
-use crate::{Http, HttpConnect};
+use crate::{
+    pool::{CountingConnector, PoolStats},
+    Http, HttpConnect,
+};
 use alloy_json_rpc::{RequestPacket, ResponsePacket};
 use alloy_transport::{
     utils::guess_local_url, BoxTransport, TransportConnect, TransportError, TransportErrorKind,
     TransportFut, TransportResult,
 };
-use http_body_util::{BodyExt, Full};
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
 use hyper::{
     body::{Bytes, Incoming},
-    header, Request, Response,
+    header, Request, Response, Uri,
+};
+use hyper_util::client::legacy::{
+    connect::{proxy::Tunnel, HttpConnector},
+    Error,
 };
-use hyper_util::client::legacy::Error;
-use std::{future::Future, marker::PhantomData, pin::Pin, task};
+use std::{future::Future, marker::PhantomData, pin::Pin, sync::Arc, task, time::Duration};
 use tower::{Layer, Service};
 use tracing::{debug, debug_span, trace, Instrument};
 
+/// The default service of a [`HyperClient`]: a hyper client whose connector
+/// counts the open connections, with the connector type erased.
+type Hyper = tower::util::BoxCloneSyncService<Request<Full<Bytes>>, HyperResponse, Error>;
+
 #[cfg(feature = "hyper-tls")]
-type Hyper = hyper_util::client::legacy::Client<
-    hyper_tls::HttpsConnector<hyper_util::client::legacy::connect::HttpConnector>,
+type ProxiedHyper = hyper_util::client::legacy::Client<
+    hyper_tls::HttpsConnector<Tunnel<HttpConnector>>,
     http_body_util::Full<::hyper::body::Bytes>,
 >;
 
 #[cfg(not(feature = "hyper-tls"))]
-type Hyper = hyper_util::client::legacy::Client<
-    hyper_util::client::legacy::connect::HttpConnector,
+type ProxiedHyper = hyper_util::client::legacy::Client<
+    Tunnel<HttpConnector>,
     http_body_util::Full<::hyper::body::Bytes>,
 >;
 
 /// A [`hyper`] based transport client.
 pub type HyperTransport = Http<HyperClient>;
 
+/// A [`hyper`] based client that tunnels all connections through an HTTP
+/// proxy.
+pub type ProxiedHyperClient = HyperClient<Full<Bytes>, ProxiedHyper>;
+
+/// A [`hyper`] based transport client that tunnels all connections through
+/// an HTTP proxy.
+pub type ProxiedHyperTransport = Http<ProxiedHyperClient>;
+
 impl HyperTransport {
     /// Create a new [`HyperTransport`] with the given URL and default hyper client.
     pub fn new_hyper(url: url::Url) -> Self {
         let client = HyperClient::new();
         Self::with_client(client, url)
     }
+
+    /// Create a new [`HyperTransportBuilder`] to configure the connection pool
+    /// of the hyper client.
+    pub const fn builder() -> HyperTransportBuilder {
+        HyperTransportBuilder::new()
+    }
+
+    /// Rebuild the transport with a default hyper client that tunnels all
+    /// connections through the HTTP proxy at `proxy`, using HTTP `CONNECT`.
+    ///
+    /// The configuration of the previous hyper client is not carried over.
+    /// See [`HyperTransportBuilder::build_proxied`] to configure the client.
+    pub fn with_proxy(self, proxy: Uri) -> ProxiedHyperTransport {
+        HyperTransportBuilder::new().build_proxied(self.base_url(), proxy)
+    }
+
+    /// Convert into a [`reqwest`](crate::ReqwestTransport) transport with a
+    /// default client for the same URL.
+    ///
+    /// The configuration of the hyper client is not carried over.
+    #[cfg(feature = "reqwest")]
+    pub fn into_reqwest(self) -> Http<reqwest::Client> {
+        Http::new(self.base_url())
+    }
+
+    /// Returns the number of open connections in the pool that are not
+    /// serving a request.
+    ///
+    /// Idle connections are closed after the
+    /// [idle connection timeout](HyperTransportBuilder::with_idle_connection_timeout).
+    pub fn idle_connection_count(&self) -> usize {
+        self.client().idle_connection_count()
+    }
+}
+
+/// A builder for a [`HyperTransport`], configuring the connection pool of
+/// the underlying hyper client.
+#[derive(Clone, Debug, Default)]
+pub struct HyperTransportBuilder {
+    idle_connection_timeout: Option<Duration>,
+}
+
+impl HyperTransportBuilder {
+    /// Create a new builder with the default hyper pool configuration.
+    pub const fn new() -> Self {
+        Self { idle_connection_timeout: None }
+    }
+
+    /// Sets how long an idle connection is kept in the pool before it is
+    /// closed. Default is 90 seconds.
+    pub const fn with_idle_connection_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_connection_timeout = Some(timeout);
+        self
+    }
+
+    /// Build the [`HyperClient`].
+    pub fn build_client(&self) -> HyperClient {
+        HyperClient::from_builder(&self.hyper_builder())
+    }
+
+    /// Build a [`HyperTransport`] for the given URL.
+    pub fn build(&self, url: url::Url) -> HyperTransport {
+        HyperTransport::with_client(self.build_client(), url)
+    }
+
+    /// Build a [`ProxiedHyperClient`] that tunnels all connections through
+    /// the HTTP proxy at `proxy`, using HTTP `CONNECT`.
+    pub fn build_proxied_client(&self, proxy: Uri) -> ProxiedHyperClient {
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        let tunnel = Tunnel::new(proxy, http);
+
+        let builder = self.hyper_builder();
+        #[cfg(feature = "hyper-tls")]
+        let service = builder.build(hyper_tls::HttpsConnector::new_with_connector(tunnel));
+
+        #[cfg(not(feature = "hyper-tls"))]
+        let service = builder.build(tunnel);
+        HyperClient::with_service(service)
+    }
+
+    /// Build a [`ProxiedHyperTransport`] for the given URL, tunneling all
+    /// connections through the HTTP proxy at `proxy`.
+    pub fn build_proxied(&self, url: url::Url, proxy: Uri) -> ProxiedHyperTransport {
+        Http::with_client(self.build_proxied_client(proxy), url)
+    }
+
+    /// Create the hyper client builder with the configured pool settings.
+    fn hyper_builder(&self) -> hyper_util::client::legacy::Builder {
+        let mut builder =
+            hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new());
+        if let Some(timeout) = self.idle_connection_timeout {
+            // Idle connections are only reaped with a pool timer.
+            builder.pool_idle_timeout(timeout).pool_timer(hyper_util::rt::TokioTimer::new());
+        }
+        builder
+    }
 }
 
 /// A [hyper] based client that can be used with tower layers.
 #[derive(Clone, Debug)]
 pub struct HyperClient<B = Full<Bytes>, S = Hyper> {
-    service: S,
+    pub(crate) service: S,
+    /// The connection statistics of the pool, if built by this crate.
+    pub(crate) pool: Option<Arc<PoolStats>>,
     _pd: PhantomData<B>,
 }
 
@@ -54,16 +171,20 @@ pub type HyperResponseFut<T = HyperResponse, E = Error> =
 impl HyperClient {
     /// Create a new [HyperClient] with the given URL and default hyper client.
     pub fn new() -> Self {
-        let executor = hyper_util::rt::TokioExecutor::new();
+        HyperTransportBuilder::new().build_client()
+    }
+
+    /// Create a new [HyperClient] from the given hyper client builder.
+    fn from_builder(builder: &hyper_util::client::legacy::Builder) -> Self {
+        let pool = Arc::new(PoolStats::default());
 
         #[cfg(feature = "hyper-tls")]
-        let service = hyper_util::client::legacy::Client::builder(executor)
-            .build(hyper_tls::HttpsConnector::new());
+        let connector = hyper_tls::HttpsConnector::new();
 
         #[cfg(not(feature = "hyper-tls"))]
-        let service =
-            hyper_util::client::legacy::Client::builder(executor).build_http::<Full<Bytes>>();
-        Self { service, _pd: PhantomData }
+        let connector = HttpConnector::new();
+        let client = builder.build(CountingConnector::new(connector, pool.clone()));
+        Self { service: Hyper::new(client), pool: Some(pool), _pd: PhantomData }
     }
 }
 
@@ -76,7 +197,16 @@ impl Default for HyperClient {
 impl<B, S> HyperClient<B, S> {
     /// Create a new [HyperClient] with the given URL and service.
     pub const fn with_service(service: S) -> Self {
-        Self { service, _pd: PhantomData }
+        Self { service, pool: None, _pd: PhantomData }
+    }
+
+    /// Returns the number of open connections in the pool that are not
+    /// serving a request.
+    ///
+    /// Always `0` for clients created with
+    /// [`with_service`](Self::with_service), whose pool is unknown.
+    pub fn idle_connection_count(&self) -> usize {
+        self.pool.as_ref().map_or(0, |pool| pool.idle_connections())
     }
 
     /// Apply a tower [`Layer`] to this client's service.
@@ -98,7 +228,7 @@ impl<B, S> HyperClient<B, S> {
     where
         L: Layer<S>,
     {
-        HyperClient::with_service(layer.layer(self.service))
+        HyperClient { service: layer.layer(self.service), pool: self.pool, _pd: PhantomData }
     }
 }
 
@@ -112,12 +242,12 @@ where
     ResBody::Error: std::error::Error + Send + Sync + 'static,
     ResBody::Data: Send,
 {
-    async fn do_hyper(self, req: RequestPacket) -> TransportResult<ResponsePacket> {
+    async fn do_hyper(self, url: url::Url, req: RequestPacket) -> TransportResult<ResponsePacket> {
         debug!(count = req.len(), "sending request packet to server");
 
         let mut builder = hyper::Request::builder()
             .method(hyper::Method::POST)
-            .uri(self.url.as_str())
+            .uri(url.as_str())
             .header(header::CONTENT_TYPE, header::HeaderValue::from_static("application/json"));
 
         // Add any additional headers from the request packet.
@@ -125,12 +255,15 @@ where
             builder = builder.header(name, value);
         }
 
+        let limit = req.response_size_limit();
         let ser = req.serialize().map_err(TransportError::ser_err)?;
         // convert the Box<RawValue> into a hyper request<B>
         let body = ser.get().as_bytes().to_owned().into();
 
         let req = builder.body(body).map_err(TransportErrorKind::custom)?;
 
+        // Connections are busy until the response body is read.
+        let _request = self.client.pool.as_ref().map(PoolStats::track_request);
         let mut service = self.client.service;
         let resp = service.call(req).await.map_err(TransportErrorKind::custom)?;
 
@@ -141,7 +274,22 @@ where
         // Unpack data from the response body. We do this regardless of
         // the status code, as we want to return the error in the body
         // if there is one.
-        let body = resp.into_body().collect().await.map_err(TransportErrorKind::custom)?.to_bytes();
+        let body = match limit {
+            Some(limit) => Limited::new(resp.into_body(), limit)
+                .collect()
+                .await
+                .map_err(|err| {
+                    if err.is::<LengthLimitError>() {
+                        TransportErrorKind::custom_str("response too large")
+                    } else {
+                        TransportErrorKind::Custom(err).into()
+                    }
+                })?
+                .to_bytes(),
+            None => {
+                resp.into_body().collect().await.map_err(TransportErrorKind::custom)?.to_bytes()
+            }
+        };
 
         if tracing::enabled!(tracing::Level::TRACE) {
             trace!(body = %String::from_utf8_lossy(&body), "response body");
@@ -194,7 +342,45 @@ where
     #[inline]
     fn call(&mut self, req: RequestPacket) -> Self::Future {
         let this = self.clone();
-        let span = debug_span!("HyperTransport", url = %this.url);
-        Box::pin(this.do_hyper(req).instrument(span))
+        let url = this.base_url();
+        let span = debug_span!("HyperTransport", %url);
+        Box::pin(this.do_hyper(url, req).instrument(span))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{request, serve_json_rpc};
+
+    #[tokio::test]
+    async fn idle_connection_timeout() {
+        let url = serve_json_rpc().await;
+        let mut transport = HyperTransport::builder()
+            .with_idle_connection_timeout(Duration::from_millis(100))
+            .build(url);
+        assert_eq!(transport.idle_connection_count(), 0);
+
+        transport.call(request()).await.unwrap();
+        assert_eq!(transport.idle_connection_count(), 1);
+
+        for _ in 0..20 {
+            if transport.idle_connection_count() == 0 {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("idle connection was not closed");
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn into_reqwest() {
+        let url = serve_json_rpc().await;
+        let mut transport = HyperTransport::new_hyper(url.clone()).into_reqwest();
+        assert_eq!(transport.base_url(), url);
+
+        let resp = transport.call(request()).await.unwrap();
+        assert_eq!(resp.as_single().unwrap().payload.as_success().unwrap().get(), r#""0x1""#);
     }
 }
@@ -28,12 +28,30 @@ pub use layers::{AuthLayer, AuthService};
 #[cfg(all(not(target_family = "wasm"), feature = "hyper"))]
 mod hyper_transport;
 #[cfg(all(not(target_family = "wasm"), feature = "hyper"))]
+mod pool;
+#[cfg(all(not(target_family = "wasm"), feature = "hyper"))]
 #[doc(inline)]
-pub use hyper_transport::{HyperClient, HyperResponse, HyperResponseFut, HyperTransport};
+pub use hyper_transport::{
+    HyperClient, HyperResponse, HyperResponseFut, HyperTransport, HyperTransportBuilder,
+    ProxiedHyperClient, ProxiedHyperTransport,
+};
+
+#[cfg(any(feature = "reqwest", feature = "hyper"))]
+pub mod raw;
+
+#[cfg(any(feature = "reqwest", all(not(target_family = "wasm"), feature = "hyper")))]
+mod factory;
+#[cfg(all(test, any(feature = "reqwest", all(not(target_family = "wasm"), feature = "hyper"))))]
+mod test_utils;
+#[cfg(any(feature = "reqwest", all(not(target_family = "wasm"), feature = "hyper")))]
+pub use factory::TransportFactory;
 
 use alloy_transport::utils::guess_local_url;
-use core::str::FromStr;
-use std::marker::PhantomData;
+use core::{fmt, str::FromStr};
+use std::{
+    marker::PhantomData,
+    sync::{Arc, PoisonError, RwLock},
+};
 use url::Url;
 
 /// Connection details for an HTTP transport.
@@ -77,21 +95,46 @@ impl<T> FromStr for HttpConnect<T> {
 /// Currently supported clients are:
 #[cfg_attr(feature = "reqwest", doc = " - [`reqwest`](::reqwest::Client)")]
 #[cfg_attr(feature = "hyper", doc = " - [`hyper`](hyper_util::client::legacy::Client)")]
+///
+/// Clones share the same URL, which can be changed at runtime with
+/// [`set_base_url`](Self::set_base_url).
 #[derive(Clone, Debug)]
 pub struct Http<T> {
     client: T,
-    url: Url,
+    url: Arc<RwLock<Url>>,
 }
 
 impl<T> Http<T> {
     /// Create a new [`Http`] transport with a custom client.
-    pub const fn with_client(client: T, url: Url) -> Self {
-        Self { client, url }
+    pub fn with_client(client: T, url: Url) -> Self {
+        Self { client, url: Arc::new(RwLock::new(url)) }
     }
 
-    /// Set the URL.
+    /// Set the URL of this transport only, detaching it from its clones.
+    #[deprecated(note = "use `set_base_url`, which also updates the clones of the transport")]
     pub fn set_url(&mut self, url: Url) {
-        self.url = url;
+        self.url = Arc::new(RwLock::new(url));
+    }
+
+    /// Returns the URL requests are sent to.
+    pub fn base_url(&self) -> Url {
+        self.url.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Atomically replace the URL requests are sent to, for this transport
+    /// and all of its clones.
+    ///
+    /// Requests already in flight complete using the previous URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL does not use the `http` or `https` scheme.
+    pub fn set_base_url(&self, url: Url) -> Result<(), InvalidUrlError> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(InvalidUrlError(url));
+        }
+        *self.url.write().unwrap_or_else(PoisonError::into_inner) = url;
+        Ok(())
     }
 
     /// Set the client.
@@ -105,7 +148,7 @@ impl<T> Http<T> {
     /// possible. It simply returns `true` if the connection has no hostname,
     /// or the hostname is `localhost` or `127.0.0.1`.
     pub fn guess_local(&self) -> bool {
-        guess_local_url(&self.url)
+        guess_local_url(self.base_url())
     }
 
     /// Get a reference to the client.
@@ -113,8 +156,92 @@ impl<T> Http<T> {
         &self.client
     }
 
-    /// Get a reference to the URL.
-    pub fn url(&self) -> &str {
-        self.url.as_ref()
+    /// Get the URL as a string.
+    pub fn url(&self) -> String {
+        self.base_url().into()
+    }
+}
+
+/// Error returned by [`Http::set_base_url`] for a URL that does not use the
+/// `http` or `https` scheme.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidUrlError(Url);
+
+impl InvalidUrlError {
+    /// Returns the rejected URL.
+    pub const fn url(&self) -> &Url {
+        &self.0
+    }
+}
+
+impl fmt::Display for InvalidUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid HTTP transport URL {}: expected an http or https scheme", self.0)
+    }
+}
+
+impl std::error::Error for InvalidUrlError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn set_base_url() {
+        let transport = Http::with_client((), url("http://localhost:8545"));
+        assert_eq!(transport.url(), "http://localhost:8545/");
+
+        transport.set_base_url(url("https://example.com/a")).unwrap();
+        transport.set_base_url(url("https://example.com/b")).unwrap();
+        assert_eq!(transport.url(), "https://example.com/b");
+        assert_eq!(transport.base_url(), url("https://example.com/b"));
+        assert!(!transport.guess_local());
+    }
+
+    #[test]
+    fn set_base_url_rejects_scheme() {
+        let transport = Http::with_client((), url("http://localhost:8545"));
+        let err = transport.set_base_url(url("ws://localhost:8546")).unwrap_err();
+        assert_eq!(err.url(), &url("ws://localhost:8546"));
+        assert_eq!(transport.url(), "http://localhost:8545/");
+    }
+
+    #[test]
+    fn clones_share_url() {
+        let transport = Http::with_client((), url("http://localhost:8545"));
+        let clone = transport.clone();
+
+        clone.set_base_url(url("https://example.com")).unwrap();
+        assert_eq!(transport.url(), "https://example.com/");
+
+        // Clones made after an update share later updates too.
+        let late = transport.clone();
+        transport.set_base_url(url("https://example.org")).unwrap();
+        assert_eq!(clone.url(), "https://example.org/");
+        assert_eq!(late.url(), "https://example.org/");
+    }
+
+    #[test]
+    #[cfg(all(feature = "reqwest", not(target_family = "wasm")))]
+    fn with_proxy() {
+        let proxy = reqwest::Proxy::all("http://localhost:3128").unwrap();
+        let transport = Http::new(url("http://localhost:8545")).with_proxy(proxy).unwrap();
+        assert_eq!(transport.url(), "http://localhost:8545/");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn set_url_detaches() {
+        let transport = Http::with_client((), url("http://localhost:8545"));
+        let mut detached = transport.clone();
+        detached.set_url(url("https://example.com"));
+
+        transport.set_base_url(url("https://example.org")).unwrap();
+        assert_eq!(detached.url(), "https://example.com/");
+        assert_eq!(transport.url(), "https://example.org/");
     }
 }
@@ -0,0 +1,151 @@
+//! Connection statistics of the hyper connection pool.
+//!
+//! hyper does not expose the state of its pool, so connections are counted by
+//! wrapping the connector, and requests by the transport.
+
+use hyper::{
+    rt::{Read, ReadBufCursor, Write},
+    Uri,
+};
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tower::Service;
+
+/// Counts the open connections and active requests of a hyper client.
+#[derive(Debug, Default)]
+pub(crate) struct PoolStats {
+    connections: AtomicUsize,
+    requests: AtomicUsize,
+}
+
+impl PoolStats {
+    /// Returns the number of open connections that are not serving a request.
+    pub(crate) fn idle_connections(&self) -> usize {
+        let connections = self.connections.load(Ordering::Relaxed);
+        connections.saturating_sub(self.requests.load(Ordering::Relaxed))
+    }
+
+    /// Track a request until the returned guard is dropped.
+    pub(crate) fn track_request(self: &Arc<Self>) -> RequestGuard {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        RequestGuard(self.clone())
+    }
+}
+
+/// Tracks an active request of [`PoolStats`].
+#[derive(Debug)]
+pub(crate) struct RequestGuard(Arc<PoolStats>);
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        self.0.requests.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A connector that counts the connections it opens, for
+/// [`HyperClient::idle_connection_count`](crate::HyperClient::idle_connection_count).
+#[derive(Clone, Debug)]
+pub(crate) struct CountingConnector<C> {
+    inner: C,
+    stats: Arc<PoolStats>,
+}
+
+impl<C> CountingConnector<C> {
+    pub(crate) const fn new(inner: C, stats: Arc<PoolStats>) -> Self {
+        Self { inner, stats }
+    }
+}
+
+impl<C> Service<Uri> for CountingConnector<C>
+where
+    C: Service<Uri>,
+    C::Future: Send + 'static,
+{
+    type Response = CountedConnection<C::Response>;
+    type Error = C::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let fut = self.inner.call(uri);
+        let stats = self.stats.clone();
+        Box::pin(async move {
+            let inner = fut.await?;
+            stats.connections.fetch_add(1, Ordering::Relaxed);
+            Ok(CountedConnection { inner, stats })
+        })
+    }
+}
+
+/// A connection opened by a [`CountingConnector`], counted until it is
+/// dropped.
+#[derive(Debug)]
+pub(crate) struct CountedConnection<T> {
+    inner: T,
+    stats: Arc<PoolStats>,
+}
+
+impl<T> Drop for CountedConnection<T> {
+    fn drop(&mut self) {
+        self.stats.connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl<T: Connection> Connection for CountedConnection<T> {
+    fn connected(&self) -> Connected {
+        self.inner.connected()
+    }
+}
+
+impl<T: Read + Unpin> Read for CountedConnection<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<T: Write + Unpin> Write for CountedConnection<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+}
@@ -0,0 +1,112 @@
+//! Raw HTTP requests, bypassing JSON-RPC serialization.
+//!
+//! Some non-standard Ethereum forks reuse HTTP as the transport but encode
+//! their RPC messages differently, e.g. with MessagePack or protobuf. The
+//! [`SendRaw`] trait is an escape hatch for talking to these nodes.
+//!
+//! Raw requests skip everything the [`Http`] transport does for JSON-RPC
+//! packets: no serialization, no request headers from the packet, no response
+//! size limit and no response deserialization. Prefer sending requests
+//! through an `RpcClient` wherever possible.
+//!
+//! ```no_run
+//! # async fn example() -> alloy_transport::TransportResult<()> {
+//! use alloy_transport_http::{raw::SendRaw, Http};
+//!
+//! let http = Http::new("http://localhost:8545".parse().unwrap());
+//! let body = vec![0x81, 0xa2, 0x69, 0x64, 0x01].into();
+//! let resp = http.send_raw(body, "application/msgpack").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::Http;
+use alloy_transport::{impl_future, TransportErrorKind, TransportResult};
+use bytes::Bytes;
+
+/// Send raw HTTP POST requests over an [`Http`] transport.
+///
+/// See the [module documentation](self) for more details.
+pub trait SendRaw {
+    /// Send a raw HTTP POST request with the given body and `Content-Type`
+    /// header, returning the raw response body.
+    ///
+    /// Non-success status codes are returned as
+    /// [`TransportErrorKind::HttpError`].
+    fn send_raw(
+        &self,
+        body: Bytes,
+        content_type: &str,
+    ) -> impl_future!(<Output = TransportResult<Bytes>>);
+}
+
+/// Turn the response status and body into the result of a raw request.
+fn raw_response(status: u16, body: Bytes) -> TransportResult<Bytes> {
+    if !(200..300).contains(&status) {
+        return Err(TransportErrorKind::http_error(
+            status,
+            String::from_utf8_lossy(&body).into_owned(),
+        ));
+    }
+    Ok(body)
+}
+
+#[cfg(feature = "reqwest")]
+impl SendRaw for Http<reqwest::Client> {
+    fn send_raw(
+        &self,
+        body: Bytes,
+        content_type: &str,
+    ) -> impl_future!(<Output = TransportResult<Bytes>>) {
+        let request = self
+            .client
+            .post(self.base_url())
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(body);
+        async move {
+            let resp = request.send().await.map_err(TransportErrorKind::custom)?;
+            let status = resp.status().as_u16();
+            let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+            raw_response(status, body)
+        }
+    }
+}
+
+#[cfg(all(not(target_family = "wasm"), feature = "hyper"))]
+impl<B, S, ResBody> SendRaw for Http<crate::HyperClient<B, S>>
+where
+    S: tower::Service<hyper::Request<B>, Response = hyper::Response<ResBody>>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    S::Future: Send,
+    S::Error: std::error::Error + Send + Sync + 'static,
+    B: From<Bytes> + Send + 'static,
+    ResBody: http_body_util::BodyExt + Send + 'static,
+    ResBody::Error: std::error::Error + Send + Sync + 'static,
+    ResBody::Data: Send,
+{
+    fn send_raw(
+        &self,
+        body: Bytes,
+        content_type: &str,
+    ) -> impl_future!(<Output = TransportResult<Bytes>>) {
+        let request = hyper::Request::builder()
+            .method(hyper::Method::POST)
+            .uri(self.base_url().as_str())
+            .header(hyper::header::CONTENT_TYPE, content_type)
+            .body(B::from(body));
+        let mut service = self.client.service.clone();
+        let request_stats = self.client.pool.as_ref().map(crate::pool::PoolStats::track_request);
+        async move {
+            let _request = request_stats;
+            let request = request.map_err(TransportErrorKind::custom)?;
+            let resp = service.call(request).await.map_err(TransportErrorKind::custom)?;
+            let status = resp.status().as_u16();
+            let body =
+                resp.into_body().collect().await.map_err(TransportErrorKind::custom)?.to_bytes();
+            raw_response(status, body)
+        }
+    }
+}
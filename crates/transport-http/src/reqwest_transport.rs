@@ -31,13 +31,50 @@ impl TransportConnect for ReqwestConnect {
 impl Http<Client> {
     /// Create a new [`Http`] transport.
     pub fn new(url: Url) -> Self {
-        Self { client: Default::default(), url }
+        Self::with_client(Default::default(), url)
     }
 
-    async fn do_reqwest(self, req: RequestPacket) -> TransportResult<ResponsePacket> {
+    /// Rebuild the client to send all requests through the given proxy.
+    ///
+    /// The configuration of the previous client is not carried over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client cannot be built, e.g. if the TLS backend
+    /// fails to initialize.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_proxy(self, proxy: reqwest::Proxy) -> Result<Self, TransportError> {
+        let client = Client::builder().proxy(proxy).build().map_err(TransportErrorKind::custom)?;
+        Ok(Self { client, ..self })
+    }
+
+    /// Rebuild the client with the proxies configured in the `HTTP_PROXY` and
+    /// `HTTPS_PROXY` environment variables, or their lowercase variants.
+    ///
+    /// Requests are sent directly if neither variable is set. The
+    /// configuration of the previous client is not carried over.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_proxy_from_env(self) -> Result<Self, reqwest::Error> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .or_else(|_| std::env::var(name.to_lowercase()))
+                .ok()
+                .filter(|value| !value.is_empty())
+        };
+        let mut builder = Client::builder().no_proxy();
+        if let Some(proxy) = var("HTTP_PROXY") {
+            builder = builder.proxy(reqwest::Proxy::http(proxy)?);
+        }
+        if let Some(proxy) = var("HTTPS_PROXY") {
+            builder = builder.proxy(reqwest::Proxy::https(proxy)?);
+        }
+        Ok(Self { client: builder.build()?, ..self })
+    }
+
+    async fn do_reqwest(self, url: Url, req: RequestPacket) -> TransportResult<ResponsePacket> {
         let resp = self
             .client
-            .post(self.url)
+            .post(url)
             .json(&req)
             .headers(req.headers())
             .send()
@@ -50,7 +87,10 @@ impl Http<Client> {
         // Unpack data from the response body. We do this regardless of
         // the status code, as we want to return the error in the body
         // if there is one.
-        let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+        let body = match req.response_size_limit() {
+            Some(limit) => read_limited(resp, limit).await?.into(),
+            None => resp.bytes().await.map_err(TransportErrorKind::custom)?,
+        };
 
         if tracing::enabled!(tracing::Level::TRACE) {
             trace!(body = %String::from_utf8_lossy(&body), "response body");
@@ -73,6 +113,23 @@ impl Http<Client> {
     }
 }
 
+/// Read the response body, failing as soon as it exceeds `limit` bytes.
+async fn read_limited(mut resp: reqwest::Response, limit: usize) -> TransportResult<Vec<u8>> {
+    let too_large = || TransportErrorKind::custom_str("response too large");
+    if resp.content_length().is_some_and(|len| len > limit as u64) {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = resp.chunk().await.map_err(TransportErrorKind::custom)? {
+        if body.len() + chunk.len() > limit {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
 impl Service<RequestPacket> for Http<reqwest::Client> {
     type Response = ResponsePacket;
     type Error = TransportError;
@@ -87,7 +144,8 @@ impl Service<RequestPacket> for Http<reqwest::Client> {
     #[inline]
     fn call(&mut self, req: RequestPacket) -> Self::Future {
         let this = self.clone();
-        let span = debug_span!("ReqwestTransport", url = %this.url);
-        Box::pin(this.do_reqwest(req).instrument(span))
+        let url = this.base_url();
+        let span = debug_span!("ReqwestTransport", %url);
+        Box::pin(this.do_reqwest(url, req).instrument(span))
     }
 }
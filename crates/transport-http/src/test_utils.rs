@@ -0,0 +1,62 @@
+use alloy_json_rpc::{Id, Request, RequestPacket};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+};
+use url::Url;
+
+/// Serve keep-alive HTTP/1.1 connections on localhost, answering every
+/// request with a JSON-RPC response with ID `0` and result `"0x1"`.
+pub(crate) async fn serve_json_rpc() -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let mut stream = BufReader::new(stream);
+                while let Some(len) = read_head(&mut stream).await {
+                    let mut body = vec![0; len];
+                    if stream.read_exact(&mut body).await.is_err() {
+                        break;
+                    }
+                    let body = r#"{"jsonrpc":"2.0","id":0,"result":"0x1"}"#;
+                    let resp = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+                        body.len()
+                    );
+                    if stream.write_all(resp.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    format!("http://{addr}").parse().unwrap()
+}
+
+/// Read the head of a request, returning its content length, or `None` once
+/// the connection is closed.
+async fn read_head<R: AsyncBufReadExt + Unpin>(stream: &mut R) -> Option<usize> {
+    let mut len = 0;
+    loop {
+        let mut line = String::new();
+        if stream.read_line(&mut line).await.ok()? == 0 {
+            return None;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Some(len);
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                len = value.trim().parse().ok()?;
+            }
+        }
+    }
+}
+
+/// A request matching the response of [`serve_json_rpc`].
+pub(crate) fn request() -> RequestPacket {
+    Request::new("eth_blockNumber", Id::Number(0), ()).serialize().unwrap().into()
+}
@@ -68,3 +68,48 @@ impl_connect!(std::ffi::CString => |s| {
     use std::os::unix::ffi::OsStrExt;
     std::ffi::OsStr::from_bytes(s.to_bytes())
 });
+
+/// An already connected unix socket stream, for use with [`IpcConnect`].
+///
+/// This allows exercising the IPC transport without a real node, e.g. in
+/// tests, see [`IpcConnect::connected_pair`]. The stream can only be used to
+/// connect once, so reconnection attempts will fail.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct ConnectedStream(std::sync::Mutex<Option<tokio::net::UnixStream>>);
+
+#[cfg(unix)]
+impl IpcConnect<ConnectedStream> {
+    /// Create a new IPC connection object from the client end of an already
+    /// connected unix socket.
+    pub const fn from_pair(client: tokio::net::UnixStream) -> Self {
+        Self { inner: ConnectedStream(std::sync::Mutex::new(Some(client))) }
+    }
+
+    /// Create a new IPC connection object backed by one end of a
+    /// `socketpair(2)`, returning the other end.
+    ///
+    /// Tests can simulate server responses by writing to the returned stream.
+    pub fn connected_pair() -> io::Result<(Self, tokio::net::UnixStream)> {
+        let (client, server) = tokio::net::UnixStream::pair()?;
+        Ok((Self::from_pair(client), server))
+    }
+}
+
+#[cfg(unix)]
+impl alloy_pubsub::PubSubConnect for IpcConnect<ConnectedStream> {
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    async fn connect(
+        &self,
+    ) -> Result<alloy_pubsub::ConnectionHandle, alloy_transport::TransportError> {
+        use interprocess::os::unix::uds_local_socket::tokio::Stream;
+
+        let stream = self.inner.0.lock().unwrap().take().ok_or_else(|| {
+            alloy_transport::TransportErrorKind::custom_str("connected IPC stream was already used")
+        })?;
+        Ok(crate::IpcBackend::from_stream(Stream::from(stream).into()))
+    }
+}
@@ -20,6 +20,8 @@ use tokio::{
 use tokio_util::io::poll_read_buf;
 
 mod connect;
+#[cfg(unix)]
+pub use connect::ConnectedStream;
 pub use connect::IpcConnect;
 
 #[cfg(feature = "mock")]
@@ -40,16 +42,21 @@ impl IpcBackend {
     /// Connect to a local socket. Either a unix socket or a windows named pipe.
     async fn connect(name: Name<'_>) -> Result<alloy_pubsub::ConnectionHandle> {
         let stream = LocalSocketStream::connect(name).await?;
+        Ok(Self::from_stream(stream))
+    }
+
+    /// Spawn a backend on an already connected local socket.
+    fn from_stream(stream: LocalSocketStream) -> alloy_pubsub::ConnectionHandle {
         let (handle, interface) = alloy_pubsub::ConnectionHandle::new();
         let backend = Self { stream, interface };
         backend.spawn();
-        Ok(handle)
+        handle
     }
 
     fn spawn(mut self) {
         let fut = async move {
             let (read, mut writer) = self.stream.split();
-            let mut read = ReadJsonStream::new(read).fuse();
+            let mut read = ReadJsonStream::<_, IpcItem>::new(read).fuse();
 
             let err = loop {
                 select! {
@@ -74,7 +81,7 @@ impl IpcBackend {
                     item = read.next() => {
                         match item {
                             Some(item) => {
-                                if self.interface.send_to_frontend(item).is_err() {
+                                if item.0.into_iter().any(|item| self.interface.send_to_frontend(item).is_err()) {
                                     debug!("Frontend has gone away");
                                     break false;
                                 }
@@ -96,6 +103,48 @@ impl IpcBackend {
     }
 }
 
+/// One or more items read from the IPC socket. Batch responses arrive as a
+/// JSON array of items.
+struct IpcItem(Vec<alloy_json_rpc::PubSubItem>);
+
+impl<'de> serde::Deserialize<'de> for IpcItem {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct IpcItemVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for IpcItemVisitor {
+            type Value = IpcItem;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("a JSON-RPC item or a batch of items")
+            }
+
+            fn visit_map<A>(self, map: A) -> std::result::Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                serde::Deserialize::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+                    .map(|item| IpcItem(vec![item]))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut items = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(IpcItem(items))
+            }
+        }
+
+        deserializer.deserialize_any(IpcItemVisitor)
+    }
+}
+
 /// Default capacity for the IPC buffer.
 const CAPACITY: usize = 4096;
 
@@ -218,6 +267,111 @@ mod tests {
     use alloy_json_rpc::PubSubItem;
     use std::future::poll_fn;
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_connected_pair() {
+        use alloy_json_rpc::{Id, Request};
+        use alloy_pubsub::PubSubConnect;
+        use tokio::io::AsyncReadExt;
+
+        let (connect, mut server) = IpcConnect::connected_pair().unwrap();
+        let frontend = connect.into_service().await.unwrap();
+
+        let req = Request::new("eth_blockNumber", Id::Number(1), ()).serialize().unwrap();
+        let resp = tokio::spawn(frontend.send(req));
+
+        let mut buf = [0u8; 1024];
+        let n = server.read(&mut buf).await.unwrap();
+        assert!(std::str::from_utf8(&buf[..n]).unwrap().contains("eth_blockNumber"));
+        server.write_all(br#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#).await.unwrap();
+
+        let resp = resp.await.unwrap().unwrap();
+        assert_eq!(resp.payload.as_success().unwrap().get(), r#""0x1""#);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_batch_subscribe() {
+        use alloy_pubsub::{PubSubConnect, SubscribeRequest};
+        use tokio::io::AsyncReadExt;
+
+        let (connect, mut server) = IpcConnect::connected_pair().unwrap();
+        let frontend = connect.into_service().await.unwrap();
+
+        let reqs = vec![
+            SubscribeRequest::new("newHeads"),
+            SubscribeRequest::new("logs").with_params(serde_json::json!({})).unwrap(),
+        ];
+        let subs = tokio::spawn(frontend.batch_subscribe(reqs));
+
+        let mut buf = [0u8; 1024];
+        let n = server.read(&mut buf).await.unwrap();
+        let batch: Vec<serde_json::Value> = serde_json::from_slice(&buf[..n]).unwrap();
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch[1]["params"], serde_json::json!(["logs", {}]));
+
+        let resp = serde_json::json!([
+            { "jsonrpc": "2.0", "id": batch[0]["id"], "result": "0x1" },
+            { "jsonrpc": "2.0", "id": batch[1]["id"], "error": { "code": -32602, "message": "bad filter" } },
+        ]);
+        server.write_all(resp.to_string().as_bytes()).await.unwrap();
+
+        let subs = subs.await.unwrap();
+        assert!(subs[0].is_ok());
+        assert!(subs[1].as_ref().unwrap_err().as_error_resp().is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_subscription_limit() {
+        use alloy_json_rpc::{Id, Request};
+        use alloy_pubsub::PubSubConnect;
+        use tokio::io::AsyncReadExt;
+
+        let (connect, mut server) = IpcConnect::connected_pair().unwrap();
+        let frontend = connect.into_service().await.unwrap().with_subscription_limit(1);
+
+        let sub = |id, kind: &'static str| {
+            Request::new("eth_subscribe", Id::Number(id), (kind,)).serialize().unwrap()
+        };
+        let first = tokio::spawn(frontend.send(sub(1, "newHeads")));
+
+        let mut buf = [0u8; 1024];
+        let n = server.read(&mut buf).await.unwrap();
+        assert!(std::str::from_utf8(&buf[..n]).unwrap().contains("newHeads"));
+        server.write_all(br#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#).await.unwrap();
+        assert!(first.await.unwrap().is_ok());
+
+        let err = frontend.send(sub(2, "logs")).await.unwrap_err();
+        assert_eq!(err.to_string(), "subscription limit reached");
+
+        // Re-subscribing with the same parameters reuses the subscription.
+        let again = tokio::spawn(frontend.send(sub(3, "newHeads")));
+        let n = server.read(&mut buf).await.unwrap();
+        assert!(std::str::from_utf8(&buf[..n]).unwrap().contains("newHeads"));
+        server.write_all(br#"{"jsonrpc":"2.0","id":3,"result":"0x2"}"#).await.unwrap();
+        assert!(again.await.unwrap().is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_notification_stream() {
+        use alloy_pubsub::PubSubConnect;
+
+        let (connect, mut server) = IpcConnect::connected_pair().unwrap();
+        let frontend = connect.into_service().await.unwrap();
+        let mut notifications = frontend.notification_stream();
+
+        server
+            .write_all(br#"{"jsonrpc":"2.0","method":"vendor_ping","params":["0x1"]}"#)
+            .await
+            .unwrap();
+
+        let (method, params) = notifications.next().await.unwrap();
+        assert_eq!(method, "vendor_ping");
+        assert_eq!(params.get(), r#"["0x1"]"#);
+    }
+
     #[tokio::test]
     async fn test_partial_stream() {
         let mock = tokio_test::io::Builder::new()
@@ -9,16 +9,31 @@
 #[macro_use]
 extern crate tracing;
 
+use alloy_json_rpc::PubSubItem;
 use alloy_pubsub::ConnectionInterface;
 
 #[cfg(not(target_family = "wasm"))]
 mod native;
 #[cfg(not(target_family = "wasm"))]
-pub use native::{WebSocketConfig, WsConnect};
+pub use native::{
+    PauseHandle, WebSocketConfig, WsConnect, WsDisconnectReason, DEFAULT_KEEPALIVE_SECS,
+};
 
-#[cfg(not(target_family = "wasm"))]
+#[cfg(all(feature = "ws-rustls", feature = "ws-native-tls", not(feature = "__allow-both-tls")))]
+compile_error!(
+    "the `ws-rustls` and `ws-native-tls` features of `alloy-transport-ws` are mutually exclusive"
+);
+
+#[cfg(all(not(target_family = "wasm"), feature = "ws-rustls"))]
 use rustls as _;
 
+#[cfg(all(
+    not(target_family = "wasm"),
+    feature = "ws-rustls",
+    any(test, feature = "danger-accept-invalid-certs")
+))]
+mod danger;
+
 #[cfg(target_family = "wasm")]
 mod wasm;
 #[cfg(target_family = "wasm")]
@@ -37,6 +52,14 @@ pub struct WsBackend<T> {
 
     /// The interface to the connection.
     pub(crate) interface: ConnectionInterface,
+
+    /// The maximum size of outgoing messages, in bytes.
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) message_size_limit: Option<usize>,
+
+    /// Whether sending messages is paused, shared with the [`PauseHandle`].
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) pause: std::sync::Arc<native::PauseState>,
 }
 
 impl<T> WsBackend<T> {
@@ -45,12 +68,21 @@ impl<T> WsBackend<T> {
     pub fn handle_text(&mut self, text: &str) -> Result<(), ()> {
         trace!(%text, "received message from websocket");
 
-        match serde_json::from_str(text) {
-            Ok(item) => {
-                trace!(?item, "deserialized message");
-                if let Err(err) = self.interface.send_to_frontend(item) {
-                    error!(item=?err.0, "failed to send deserialized item to handler");
-                    return Err(());
+        // Batch responses arrive as a JSON array of items.
+        let items = if text.trim_start().starts_with('[') {
+            serde_json::from_str::<Vec<PubSubItem>>(text)
+        } else {
+            serde_json::from_str(text).map(|item| vec![item])
+        };
+
+        match items {
+            Ok(items) => {
+                for item in items {
+                    trace!(?item, "deserialized message");
+                    if let Err(err) = self.interface.send_to_frontend(item) {
+                        error!(item=?err.0, "failed to send deserialized item to handler");
+                        return Err(());
+                    }
                 }
             }
             Err(err) => {
@@ -17,15 +17,27 @@ pub struct WsConnect {
     /// Max number of retries before failing and exiting the connection.
     /// Default is 10.
     max_retries: u32,
-    /// The interval between retries.
+    /// The interval before the first retry.
     /// Default is 3 seconds.
-    retry_interval: Duration,
+    retry_initial_interval: Duration,
+    /// The factor by which the retry interval grows after each failed attempt.
+    /// Default is 2.0.
+    retry_backoff_factor: f64,
+    /// The upper bound for the interval between retries.
+    /// Default is 30 seconds.
+    retry_max_interval: Duration,
 }
 
 impl WsConnect {
     /// Creates a new websocket connection configuration.
     pub fn new<S: Into<String>>(url: S) -> Self {
-        Self { url: url.into(), max_retries: 10, retry_interval: Duration::from_secs(3) }
+        Self {
+            url: url.into(),
+            max_retries: 10,
+            retry_initial_interval: Duration::from_secs(3),
+            retry_backoff_factor: 2.0,
+            retry_max_interval: Duration::from_secs(30),
+        }
     }
 
     /// Sets the max number of retries before failing and exiting the connection.
@@ -35,10 +47,28 @@ impl WsConnect {
         self
     }
 
-    /// Sets the interval between retries.
+    /// Sets the interval before the first retry.
     /// Default is 3 seconds.
+    ///
+    /// Subsequent retries back off exponentially, see
+    /// [`with_retry_backoff_factor`](Self::with_retry_backoff_factor).
     pub const fn with_retry_interval(mut self, retry_interval: Duration) -> Self {
-        self.retry_interval = retry_interval;
+        self.retry_initial_interval = retry_interval;
+        self
+    }
+
+    /// Sets the factor by which the retry interval grows after each failed
+    /// attempt. A factor of `1.0` results in a constant retry interval.
+    /// Default is 2.0.
+    pub const fn with_retry_backoff_factor(mut self, retry_backoff_factor: f64) -> Self {
+        self.retry_backoff_factor = retry_backoff_factor;
+        self
+    }
+
+    /// Sets the upper bound for the interval between retries.
+    /// Default is 30 seconds.
+    pub const fn with_retry_max_interval(mut self, retry_max_interval: Duration) -> Self {
+        self.retry_max_interval = retry_max_interval;
         self
     }
 
@@ -62,7 +92,11 @@ impl PubSubConnect for WsConnect {
 
         backend.spawn();
 
-        Ok(handle.with_max_retries(self.max_retries).with_retry_interval(self.retry_interval))
+        Ok(handle
+            .with_max_retries(self.max_retries)
+            .with_retry_interval(self.retry_initial_interval)
+            .with_retry_backoff_factor(self.retry_backoff_factor)
+            .with_retry_max_interval(self.retry_max_interval))
     }
 }
 
@@ -50,6 +50,64 @@ impl Authorization {
     pub fn raw(token: impl Into<String>) -> Self {
         Self::Raw(token.into())
     }
+
+    /// Parse an `Authorization` header value, e.g. as received by a server.
+    ///
+    /// The `Bearer` and `Basic` schemes are recognized, case-insensitively.
+    /// Basic credentials must be valid base64. Other schemes are rejected, and
+    /// may be kept with [`Authorization::raw`] instead.
+    pub fn from_header_value(value: &http::HeaderValue) -> Result<Self, AuthParseError> {
+        let err = |reason| AuthParseError::new(value, reason);
+        let value = value.to_str().map_err(|_| err("header value is not visible ASCII"))?;
+        let (scheme, token) = value.split_once(' ').ok_or_else(|| err("missing scheme"))?;
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(err("missing credentials"));
+        }
+
+        if scheme.eq_ignore_ascii_case("bearer") {
+            Ok(Self::bearer(token))
+        } else if scheme.eq_ignore_ascii_case("basic") {
+            general_purpose::STANDARD
+                .decode(token)
+                .map_err(|_| err("basic credentials are not valid base64"))?;
+            Ok(Self::Basic(token.to_string()))
+        } else {
+            Err(err("unsupported scheme"))
+        }
+    }
+}
+
+/// Error returned by [`Authorization::from_header_value`].
+///
+/// The credentials of the header value are redacted, so the error is safe to
+/// log.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("invalid authorization header `{redacted}`: {reason}")]
+pub struct AuthParseError {
+    redacted: String,
+    reason: &'static str,
+}
+
+impl AuthParseError {
+    fn new(value: &http::HeaderValue, reason: &'static str) -> Self {
+        // Keep the scheme, if any, and redact everything after it.
+        let redacted = match value.to_str().ok().and_then(|value| value.split_once(' ')) {
+            Some((scheme, _)) => format!("{scheme} <redacted>"),
+            None => "<redacted>".to_string(),
+        };
+        Self { redacted, reason }
+    }
+
+    /// Returns the header value, with the credentials redacted.
+    pub fn redacted_value(&self) -> &str {
+        &self.redacted
+    }
+
+    /// Returns the reason the header value could not be parsed.
+    pub const fn reason(&self) -> &'static str {
+        self.reason
+    }
 }
 
 impl fmt::Display for Authorization {
@@ -109,6 +167,24 @@ mod tests {
         assert_eq!(auth, Authorization::Basic(general_purpose::STANDARD.encode("user:pass")));
     }
 
+    #[test]
+    fn test_from_header_value() {
+        use http::HeaderValue;
+
+        let parse = |value| Authorization::from_header_value(&HeaderValue::from_static(value));
+        assert_eq!(parse("Bearer token").unwrap(), Authorization::bearer("token"));
+        assert_eq!(parse("basic dXNlcjpwYXNz").unwrap(), Authorization::basic("user", "pass"));
+
+        let err = parse("Basic not-base64!").unwrap_err();
+        assert_eq!(err.redacted_value(), "Basic <redacted>");
+        assert_eq!(err.reason(), "basic credentials are not valid base64");
+        assert!(!err.to_string().contains("not-base64"));
+
+        assert_eq!(parse("Digest secret").unwrap_err().reason(), "unsupported scheme");
+        assert_eq!(parse("secret").unwrap_err().redacted_value(), "<redacted>");
+        assert_eq!(parse("Bearer ").unwrap_err().reason(), "missing credentials");
+    }
+
     #[test]
     fn test_raw() {
         let auth = Authorization::raw("raw_token");
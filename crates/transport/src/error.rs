@@ -36,6 +36,10 @@ pub enum TransportErrorKind {
     #[error("{0}")]
     HttpError(#[from] HttpError),
 
+    /// The request did not complete before its deadline.
+    #[error("request timed out")]
+    Timeout,
+
     /// Custom error.
     #[error("{0}")]
     Custom(#[source] Box<dyn StdError + Send + Sync + 'static>),
@@ -73,6 +77,19 @@ impl TransportErrorKind {
         RpcError::Transport(Self::PubsubUnavailable)
     }
 
+    /// Instantiate a new `TransportError::Timeout`.
+    pub const fn timeout() -> TransportError {
+        RpcError::Transport(Self::Timeout)
+    }
+
+    /// Instantiate a new `TransportError` from a JSON-RPC error response with
+    /// the given code and message, as if it had been returned by a server.
+    ///
+    /// This is primarily useful for mocking server errors in tests.
+    pub fn json_rpc_error(code: i64, message: &str) -> TransportError {
+        RpcError::ErrorResp(ErrorPayload::new(code, message.to_owned()))
+    }
+
     /// Instantiate a new `TransportError::HttpError`.
     pub const fn http_error(status: u16, body: String) -> TransportError {
         RpcError::Transport(Self::HttpError(HttpError { status, body }))
@@ -88,6 +105,11 @@ impl TransportErrorKind {
         matches!(self, Self::BackendGone)
     }
 
+    /// Returns true if this is [`TransportErrorKind::Timeout`].
+    pub const fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout)
+    }
+
     /// Returns true if this is [`TransportErrorKind::HttpError`].
     pub const fn is_http_error(&self) -> bool {
         matches!(self, Self::HttpError(_))
@@ -226,6 +248,15 @@ mod tests {
         assert!(TransportError::ErrorResp(err).is_retryable());
     }
 
+    #[test]
+    fn test_json_rpc_error() {
+        let err = TransportErrorKind::json_rpc_error(-32000, "execution reverted");
+        let payload = err.as_error_resp().unwrap();
+        assert_eq!(payload.code, -32000);
+        assert_eq!(payload.message, "execution reverted");
+        assert!(payload.data.is_none());
+    }
+
     #[test]
     fn test_retry_error_429() {
         let err = r#"{"code":429,"event":-33200,"message":"Too Many Requests","details":"You have surpassed your allowed throughput limit. Reduce the amount of requests per second or upgrade for more capacity."}"#;
@@ -0,0 +1,186 @@
+use crate::{time::Instant, Transport, TransportError, TransportFut};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use parking_lot::Mutex;
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::Service;
+use tracing::{info, warn};
+
+/// The default duration after which the primary transport is re-tried
+/// following a failure.
+const DEFAULT_FAIL_BACK_AFTER: Duration = Duration::from_secs(30);
+
+/// A transport that routes requests to a primary transport `A`, and falls
+/// back to a secondary transport `B` when the primary fails.
+///
+/// Only transport-level failures trigger a fall back. JSON-RPC error
+/// responses are returned to the caller as-is, as the secondary would most
+/// likely respond with the same error.
+///
+/// After a primary failure, requests are routed to the secondary directly
+/// until the [`fail_back_after`] duration has elapsed, after which the
+/// primary is re-tried.
+///
+/// Unlike the [`FallbackService`], requests are never sent to both
+/// transports concurrently.
+///
+/// [`fail_back_after`]: FallbackTransport::with_fail_back_after
+/// [`FallbackService`]: crate::layers::FallbackService
+#[derive(Debug, Clone)]
+pub struct FallbackTransport<A, B> {
+    primary: A,
+    secondary: B,
+    fail_back_after: Duration,
+    /// The instant of the last primary failure, if the primary is currently
+    /// considered unhealthy.
+    failed_at: Arc<Mutex<Option<Instant>>>,
+}
+
+impl<A, B> FallbackTransport<A, B> {
+    /// Create a new fallback transport from a primary and a secondary
+    /// transport.
+    pub fn new(primary: A, secondary: B) -> Self {
+        Self {
+            primary,
+            secondary,
+            fail_back_after: DEFAULT_FAIL_BACK_AFTER,
+            failed_at: Default::default(),
+        }
+    }
+
+    /// Set the duration after which the primary transport is re-tried
+    /// following a failure.
+    ///
+    /// Default is 30 seconds.
+    pub const fn with_fail_back_after(mut self, fail_back_after: Duration) -> Self {
+        self.fail_back_after = fail_back_after;
+        self
+    }
+
+    /// Returns the duration after which the primary transport is re-tried.
+    pub const fn fail_back_after(&self) -> Duration {
+        self.fail_back_after
+    }
+
+    /// Returns a reference to the primary transport.
+    pub const fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Returns a reference to the secondary transport.
+    pub const fn secondary(&self) -> &B {
+        &self.secondary
+    }
+
+    /// Returns `true` if the primary transport last failed and is not due to
+    /// be re-tried yet.
+    pub fn is_primary_unhealthy(&self) -> bool {
+        self.failed_at.lock().is_some_and(|at| at.elapsed() < self.fail_back_after)
+    }
+}
+
+impl<A, B> FallbackTransport<A, B>
+where
+    A: Transport + Clone,
+    B: Transport + Clone,
+{
+    async fn make_request(self, req: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        if self.is_primary_unhealthy() {
+            return self.secondary.clone().call(req).await;
+        }
+
+        match self.primary.clone().call(req.clone()).await {
+            Ok(resp) => {
+                if self.failed_at.lock().take().is_some() {
+                    info!("primary transport recovered");
+                }
+                Ok(resp)
+            }
+            Err(err) if err.is_error_resp() => Err(err),
+            Err(err) => {
+                warn!(%err, "primary transport failed, falling back to secondary");
+                *self.failed_at.lock() = Some(Instant::now());
+                self.secondary.clone().call(req).await
+            }
+        }
+    }
+}
+
+impl<A, B> Service<RequestPacket> for FallbackTransport<A, B>
+where
+    A: Transport + Clone,
+    B: Transport + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Service is always ready
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let this = self.clone();
+        Box::pin(this.make_request(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Asserter, MockTransport};
+    use alloy_json_rpc::{Id, Request};
+
+    fn req() -> RequestPacket {
+        RequestPacket::Single(
+            Request::new("eth_blockNumber", Id::Number(1), ()).serialize().unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn falls_back_and_recovers() {
+        let primary = Asserter::new();
+        let secondary = Asserter::new();
+        let mut transport = FallbackTransport::new(
+            MockTransport::new(primary.clone()),
+            MockTransport::new(secondary.clone()),
+        )
+        .with_fail_back_after(Duration::from_millis(50));
+
+        // An empty asserter fails at the transport level.
+        secondary.push_success(&1);
+        let resp = transport.call(req()).await.unwrap();
+        assert_eq!(resp.single_payload().unwrap().as_success().unwrap().get(), "1");
+        assert!(transport.is_primary_unhealthy());
+
+        // The primary is skipped while unhealthy.
+        secondary.push_success(&2);
+        let resp = transport.call(req()).await.unwrap();
+        assert_eq!(resp.single_payload().unwrap().as_success().unwrap().get(), "2");
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        primary.push_success(&3);
+        let resp = transport.call(req()).await.unwrap();
+        assert_eq!(resp.single_payload().unwrap().as_success().unwrap().get(), "3");
+        assert!(!transport.is_primary_unhealthy());
+    }
+
+    #[tokio::test]
+    async fn error_responses_do_not_fall_back() {
+        let primary = Asserter::new();
+        let mut transport = FallbackTransport::new(
+            MockTransport::new(primary.clone()),
+            MockTransport::new(Asserter::new()),
+        );
+
+        primary.push_failure_msg("boom");
+        let resp = transport.call(req()).await.unwrap();
+        assert_eq!(resp.first_error_message(), Some("boom"));
+        assert!(!transport.is_primary_unhealthy());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{TransportError, TransportFut};
+use alloy_json_rpc::{Request, RequestPacket, ResponsePacket, RpcError, SerializedRequest};
+use serde_json::value::RawValue;
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// A function that mutates an outgoing request.
+type InterceptorFn = Arc<dyn Fn(&mut Request<Box<RawValue>>) + Send + Sync>;
+
+/// A Transport Layer that calls a function on every outgoing request before
+/// it is sent.
+///
+/// This is useful for testing and debugging, e.g. to inject trace IDs,
+/// override block numbers, or record requests for replay. Requests without
+/// params are passed to the function with `null` params, which are omitted
+/// again if left unchanged.
+#[derive(Clone)]
+pub struct RequestInterceptorLayer {
+    f: InterceptorFn,
+}
+
+impl RequestInterceptorLayer {
+    /// Creates a new interceptor layer calling `f` on every outgoing request.
+    pub fn new(f: impl Fn(&mut Request<Box<RawValue>>) + Send + Sync + 'static) -> Self {
+        Self { f: Arc::new(f) }
+    }
+}
+
+impl fmt::Debug for RequestInterceptorLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestInterceptorLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for RequestInterceptorLayer {
+    type Service = RequestInterceptorService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestInterceptorService { inner, f: self.f.clone() }
+    }
+}
+
+/// A Tower Service used by the [`RequestInterceptorLayer`] that mutates
+/// outgoing requests.
+#[derive(Clone)]
+pub struct RequestInterceptorService<S> {
+    /// The inner service
+    inner: S,
+    f: InterceptorFn,
+}
+
+impl<S: fmt::Debug> fmt::Debug for RequestInterceptorService<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestInterceptorService")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> RequestInterceptorService<S> {
+    fn intercept(&self, req: SerializedRequest) -> serde_json::Result<SerializedRequest> {
+        let params = req.params().map(ToOwned::to_owned);
+        let had_params = params.is_some();
+        let (meta, _) = req.decompose();
+
+        let mut request =
+            Request { meta, params: params.unwrap_or_else(|| RawValue::NULL.to_owned()) };
+        (self.f)(&mut request);
+
+        if !had_params && request.params.get() == "null" {
+            request.map_params(|_| ()).serialize()
+        } else {
+            request.serialize()
+        }
+    }
+
+    fn intercept_packet(&self, req: RequestPacket) -> serde_json::Result<RequestPacket> {
+        Ok(match req {
+            RequestPacket::Single(req) => RequestPacket::Single(self.intercept(req)?),
+            RequestPacket::Batch(reqs) => RequestPacket::Batch(
+                reqs.into_iter().map(|req| self.intercept(req)).collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+impl<S> Service<RequestPacket> for RequestInterceptorService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        match self.intercept_packet(request) {
+            Ok(request) => Box::pin(self.inner.call(request)),
+            Err(err) => Box::pin(async move { Err(RpcError::ser_err(err)) }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Response, ResponsePayload};
+    use tower::ServiceExt;
+
+    /// Responds to every request with its serialized form.
+    async fn echo(req: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let RequestPacket::Single(req) = req else { unreachable!() };
+        Ok(ResponsePacket::Single(Response {
+            id: req.id().clone(),
+            payload: ResponsePayload::Success(req.into_serialized()),
+        }))
+    }
+
+    async fn intercept(req: Request<impl alloy_json_rpc::RpcSend>) -> String {
+        let service = RequestInterceptorLayer::new(|req| {
+            req.meta.method = "eth_intercepted".into();
+            if req.params.get() == "[\"latest\"]" {
+                req.params = RawValue::from_string("[\"0x1\"]".into()).unwrap();
+            }
+        })
+        .layer(tower::service_fn(echo));
+
+        let resp = service.oneshot(RequestPacket::Single(req.serialize().unwrap())).await.unwrap();
+        resp.single_payload().unwrap().as_success().unwrap().get().to_string()
+    }
+
+    #[tokio::test]
+    async fn intercepts_requests() {
+        let req = Request::new("eth_getBalance", Id::Number(1), ["latest"]);
+        assert_eq!(
+            intercept(req).await,
+            r#"{"method":"eth_intercepted","params":["0x1"],"id":1,"jsonrpc":"2.0"}"#
+        );
+
+        let req = Request::new("eth_blockNumber", Id::Number(2), ());
+        assert_eq!(intercept(req).await, r#"{"method":"eth_intercepted","id":2,"jsonrpc":"2.0"}"#);
+    }
+}
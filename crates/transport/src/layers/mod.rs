@@ -15,3 +15,7 @@ pub use retry::{
 /// FallbackLayer
 mod fallback;
 pub use fallback::{FallbackLayer, FallbackService};
+
+/// RequestInterceptorLayer
+mod interceptor;
+pub use interceptor::{RequestInterceptorLayer, RequestInterceptorService};
@@ -9,14 +9,20 @@
 mod boxed;
 pub use boxed::{BoxTransport, IntoBoxTransport};
 
+mod mapped;
+pub use mapped::MappedTransport;
+
 mod dual;
 pub use dual::*;
 
+mod fallback;
+pub use fallback::FallbackTransport;
+
 mod connect;
 pub use connect::TransportConnect;
 
 mod common;
-pub use common::Authorization;
+pub use common::{AuthParseError, Authorization};
 
 pub mod mock;
 
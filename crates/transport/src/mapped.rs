@@ -0,0 +1,128 @@
+use crate::{TransportError, TransportFut};
+use alloy_json_rpc::{RequestPacket, ResponsePacket, ResponsePayload};
+use serde_json::value::RawValue;
+use std::{
+    fmt,
+    task::{Context, Poll},
+};
+use tower::Service;
+
+/// A [`Transport`] that transforms the payload of every successful response
+/// of an inner transport.
+///
+/// This is useful for unwrapping non-standard response envelopes, e.g.
+/// `{"result": {"data": <actual>}}` from a custom RPC gateway, before the
+/// payload reaches the deserialization layer. Error responses are passed
+/// through untouched.
+///
+/// Instantiated via [`Transport::map_response`].
+///
+/// [`Transport`]: crate::Transport
+/// [`Transport::map_response`]: crate::Transport::map_response
+#[derive(Clone)]
+pub struct MappedTransport<T, F> {
+    inner: T,
+    f: F,
+}
+
+impl<T, F> MappedTransport<T, F> {
+    /// Create a new mapped transport.
+    pub const fn new(inner: T, f: F) -> Self {
+        Self { inner, f }
+    }
+
+    /// Returns a reference to the inner transport.
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Consumes the mapped transport, returning the inner transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for MappedTransport<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedTransport").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<T, F> Service<RequestPacket> for MappedTransport<T, F>
+where
+    T: Service<
+        RequestPacket,
+        Response = ResponsePacket,
+        Error = TransportError,
+        Future = TransportFut<'static>,
+    >,
+    F: Fn(Box<RawValue>) -> Box<RawValue> + Clone + Send + Sync + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let fut = self.inner.call(req);
+        let f = self.f.clone();
+        Box::pin(async move {
+            let mut resp = fut.await?;
+            match &mut resp {
+                ResponsePacket::Single(resp) => map_payload(&mut resp.payload, &f),
+                ResponsePacket::Batch(resps) => {
+                    resps.iter_mut().for_each(|resp| map_payload(&mut resp.payload, &f))
+                }
+            }
+            Ok(resp)
+        })
+    }
+}
+
+fn map_payload<F>(payload: &mut ResponsePayload, f: &F)
+where
+    F: Fn(Box<RawValue>) -> Box<RawValue>,
+{
+    if let ResponsePayload::Success(value) = payload {
+        // Swap in a cheap placeholder to move the value out.
+        *value = f(std::mem::replace(value, RawValue::NULL.to_owned()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{Asserter, MockTransport},
+        Transport,
+    };
+    use alloy_json_rpc::{Id, Request};
+
+    #[tokio::test]
+    async fn maps_successful_responses() {
+        let asserter = Asserter::new();
+        asserter.push_success(&serde_json::json!({ "data": 42 }));
+        asserter.push_failure_msg("boom");
+
+        let mut transport = MockTransport::new(asserter).map_response(|raw: Box<RawValue>| {
+            let value: serde_json::Value = serde_json::from_str(raw.get()).unwrap();
+            serde_json::value::to_raw_value(&value["data"]).unwrap()
+        });
+
+        let req = || {
+            RequestPacket::Single(
+                Request::new("eth_blockNumber", Id::Number(1), ()).serialize().unwrap(),
+            )
+        };
+
+        let resp = transport.call(req()).await.unwrap();
+        assert_eq!(resp.single_payload().unwrap().as_success().unwrap().get(), "42");
+
+        let resp = transport.call(req()).await.unwrap();
+        assert_eq!(resp.first_error_message(), Some("boom"));
+    }
+}
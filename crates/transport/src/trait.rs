@@ -1,5 +1,6 @@
-use crate::{BoxTransport, IntoBoxTransport, TransportError, TransportFut};
-use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use crate::{BoxTransport, IntoBoxTransport, MappedTransport, TransportError, TransportFut};
+use alloy_json_rpc::{Request, RequestPacket, ResponsePacket};
+use serde_json::value::RawValue;
 use tower::Service;
 
 /// A `Transport` manages the JSON-RPC request/response lifecycle.
@@ -58,6 +59,45 @@ pub trait Transport:
     {
         self.clone().boxed()
     }
+
+    /// Wrap this transport, applying `f` to the payload of every successful
+    /// response before it reaches the deserialization layer.
+    ///
+    /// See [`MappedTransport`] for more details.
+    fn map_response<F>(self, f: F) -> MappedTransport<Self, F>
+    where
+        Self: Sized,
+        F: Fn(Box<RawValue>) -> Box<RawValue> + Clone + Send + Sync + 'static,
+    {
+        MappedTransport::new(self, f)
+    }
+
+    /// Send pre-assembled requests as a single JSON-RPC batch.
+    ///
+    /// This bypasses the batch builder of the `RpcClient`: responses are not
+    /// correlated with the requests, so the caller is responsible for
+    /// matching them, e.g. with [`ResponsePacket::find_by_id`]. Servers may
+    /// return batch responses in any order.
+    ///
+    /// An empty batch is not sent, and results in an empty response batch.
+    fn send_batch_unchecked(
+        &mut self,
+        requests: Vec<Request<Box<RawValue>>>,
+    ) -> TransportFut<'static> {
+        if requests.is_empty() {
+            return Box::pin(futures::future::ready(Ok(ResponsePacket::Batch(Default::default()))));
+        }
+
+        let packet = requests
+            .into_iter()
+            .map(Request::serialize)
+            .collect::<serde_json::Result<Vec<_>>>()
+            .map(RequestPacket::Batch);
+        match packet {
+            Ok(packet) => self.call(packet),
+            Err(err) => Box::pin(futures::future::ready(Err(TransportError::ser_err(err)))),
+        }
+    }
 }
 
 impl<T> Transport for T where
@@ -71,3 +111,31 @@ impl<T> Transport for T where
         + 'static
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Asserter, MockTransport};
+    use alloy_json_rpc::Id;
+
+    #[tokio::test]
+    async fn send_batch_unchecked() {
+        let asserter = Asserter::new();
+        asserter.push_success(&1);
+        asserter.push_success(&2);
+        let mut transport = MockTransport::new(asserter);
+
+        let params = RawValue::from_string("[]".to_string()).unwrap();
+        let requests = (1..=2)
+            .map(|id| Request::new("eth_blockNumber", Id::Number(id), params.clone()))
+            .collect();
+        let resp = transport.send_batch_unchecked(requests).await.unwrap();
+        assert_eq!(
+            resp.find_by_id(&Id::Number(2)).unwrap().payload.as_success().unwrap().get(),
+            "2"
+        );
+
+        let resp = transport.send_batch_unchecked(Vec::new()).await.unwrap();
+        assert!(resp.responses().is_empty());
+    }
+}
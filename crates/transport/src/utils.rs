@@ -1,7 +1,10 @@
 use crate::{TransportError, TransportResult};
 use serde::Serialize;
 use serde_json::value::{to_raw_value, RawValue};
-use std::future::Future;
+use std::{
+    future::Future,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+};
 use url::Url;
 
 /// Convert to a `Box<RawValue>` from a `Serialize` type, mapping the error
@@ -27,6 +30,46 @@ pub fn guess_local_url(s: impl AsRef<str>) -> bool {
     _guess_local_url(s.as_ref())
 }
 
+/// Parse a URL, prepending `scheme` to inputs that don't have one.
+///
+/// Inputs without a scheme are only accepted if their host is `localhost` or
+/// an IP address, e.g. `localhost:8545`, `127.0.0.1`, `[::1]:8545/rpc` or
+/// `::1`. If such an input has no port, `default_port` is used. Inputs with a
+/// scheme are parsed as-is.
+///
+/// ```
+/// use alloy_transport::utils::parse_url_with_default_port;
+///
+/// let url = parse_url_with_default_port("localhost", "http", 8545).unwrap();
+/// assert_eq!(url.as_str(), "http://localhost:8545/");
+/// ```
+pub fn parse_url_with_default_port(
+    s: &str,
+    scheme: &str,
+    default_port: u16,
+) -> Result<Url, url::ParseError> {
+    if s.contains("://") {
+        return Url::parse(s);
+    }
+
+    let (authority, path) = s.split_at(s.find('/').unwrap_or(s.len()));
+    if authority.starts_with("localhost:") || authority.parse::<SocketAddr>().is_ok() {
+        return Url::parse(&format!("{scheme}://{s}"));
+    }
+
+    let host = if authority.parse::<Ipv6Addr>().is_ok() {
+        format!("[{authority}]")
+    } else {
+        authority.to_string()
+    };
+    let is_ip = host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>().is_ok();
+    if host != "localhost" && !is_ip {
+        return Url::parse(s);
+    }
+
+    Url::parse(&format!("{scheme}://{host}:{default_port}{path}"))
+}
+
 #[doc(hidden)]
 pub trait Spawnable {
     /// Spawn the future as a task.
@@ -59,3 +102,33 @@ where
         wasm_bindgen_futures::spawn_local(self);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_url_default_port() {
+        let parse = |s| parse_url_with_default_port(s, "http", 8545).unwrap().to_string();
+
+        // Bare hostname.
+        assert_eq!(parse("localhost"), "http://localhost:8545/");
+        assert_eq!(parse("localhost:1234"), "http://localhost:1234/");
+        // SocketAddr.
+        assert_eq!(parse("127.0.0.1:1234"), "http://127.0.0.1:1234/");
+        assert_eq!(parse("127.0.0.1"), "http://127.0.0.1:8545/");
+        // IPv6.
+        assert_eq!(parse("::1"), "http://[::1]:8545/");
+        assert_eq!(parse("[::1]"), "http://[::1]:8545/");
+        assert_eq!(parse("[::1]:1234"), "http://[::1]:1234/");
+        // Path.
+        assert_eq!(parse("localhost/rpc"), "http://localhost:8545/rpc");
+        assert_eq!(parse("[::1]:1234/rpc/v1"), "http://[::1]:1234/rpc/v1");
+        // Explicit scheme.
+        assert_eq!(parse("https://example.com"), "https://example.com/");
+        assert_eq!(parse("ws://localhost"), "ws://localhost/");
+
+        assert!(parse_url_with_default_port("/tmp/reth.ipc", "http", 8545).is_err());
+        assert!(parse_url_with_default_port("reth.ipc", "http", 8545).is_err());
+    }
+}